@@ -0,0 +1,111 @@
+/// Generates the solution registry of the main binary and the primary year of the workspace.
+///
+/// Every `src/bin/DD.rs` and `src/bin/YYYY-DD.rs` is included as a module of `src/main.rs`, the
+/// `SOLUTION` constants defined by the `solution!` macro are collected into `SOLUTIONS`.
+/// See `src/template/registry.rs` for how the table is used.
+use std::fmt::Write;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::{env, fs};
+
+#[path = "src/template/date.rs"]
+mod date;
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/bin");
+    println!("cargo:rerun-if-env-changed=AOC_YEAR");
+
+    write_primary_year();
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+    let mut days: Vec<(String, String)> = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter_map(|entry| {
+                    let path = entry.path();
+                    let stem = path.file_stem()?.to_str()?.to_string();
+                    let is_day = path.extension()? == "rs" && is_puzzle_name(&stem);
+                    is_day.then(|| (stem.replace('-', "_"), path.display().to_string()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    days.sort();
+
+    // day modules are excluded from test builds (their tests already run as part of the bins)
+    // and from heap profiling builds (every day declares its own global allocator there).
+    let cfg = r#"#[cfg(not(any(test, feature = "dhat-heap")))]"#;
+
+    let mut out = String::new();
+
+    for (day, path) in &days {
+        writeln!(out, "{cfg}").unwrap();
+        writeln!(out, "#[allow(dead_code)]").unwrap();
+        writeln!(out, "#[path = {path:?}]").unwrap();
+        writeln!(out, "mod day{day};").unwrap();
+    }
+
+    writeln!(out, "{cfg}").unwrap();
+    writeln!(
+        out,
+        "pub const SOLUTIONS: &[advent_of_code::template::registry::Entry] = &["
+    )
+    .unwrap();
+    for (day, _) in &days {
+        writeln!(out, "    day{day}::SOLUTION,").unwrap();
+    }
+    writeln!(out, "];").unwrap();
+
+    writeln!(out, r#"#[cfg(any(test, feature = "dhat-heap"))]"#).unwrap();
+    writeln!(
+        out,
+        "pub const SOLUTIONS: &[advent_of_code::template::registry::Entry] = &[];"
+    )
+    .unwrap();
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("solutions.rs"), out).unwrap();
+}
+
+/// `DD` for days of the primary year, `YYYY-DD` for days of other years.
+fn is_puzzle_name(stem: &str) -> bool {
+    let is_number = |s: &str, len: usize| s.len() == len && s.chars().all(|c| c.is_ascii_digit());
+
+    match stem.split_once('-') {
+        Some((year, day)) => is_number(year, 4) && is_number(day, 2),
+        None => is_number(stem, 2),
+    }
+}
+
+/// The primary year is taken from `AOC_YEAR`, falling back to the latest event.
+/// See `src/template/year.rs`.
+fn write_primary_year() {
+    let year = env::var("AOC_YEAR")
+        .ok()
+        .and_then(|year| year.parse::<u16>().ok())
+        .filter(|year| *year >= 2015)
+        .unwrap_or_else(latest_event);
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(
+        Path::new(&out_dir).join("primary_year.rs"),
+        year.to_string(),
+    )
+    .unwrap();
+}
+
+/// The year of the most recent event that has started (events start on the 1st of december).
+fn latest_event() -> u16 {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+
+    let (year, month, _) = date::civil_from_timestamp(secs);
+
+    let latest = if month == 12 { year } else { year - 1 };
+    u16::try_from(latest).unwrap_or(2015)
+}
@@ -1,9 +1,13 @@
 use std::iter::successors;
 
-advent_of_code::solution!(1);
+use advent_of_code::template::{ParseResult, Solution};
+
+advent_of_code::solution!(1, Day01);
+
+pub struct Day01;
 
 fn calculate_required_fuel(mass: usize) -> usize {
-    ((mass / 3).saturating_sub(2)).max(0)
+    (mass / 3).saturating_sub(2)
 }
 
 fn calculate_module_fuel(module_mass: usize) -> usize {
@@ -18,24 +22,22 @@ fn calculate_module_fuel(module_mass: usize) -> usize {
     .sum()
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    Some(
-        input
-            .lines()
-            .filter_map(|n| n.parse().ok())
-            .map(calculate_required_fuel)
-            .sum(),
-    )
-}
+impl Solution for Day01 {
+    type Input = Vec<usize>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> ParseResult<Self::Input> {
+        Ok(input.lines().filter_map(|n| n.parse().ok()).collect())
+    }
+
+    fn part_one(masses: &Self::Input) -> Option<usize> {
+        Some(masses.iter().copied().map(calculate_required_fuel).sum())
+    }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    Some(
-        input
-            .lines()
-            .filter_map(|n| n.parse().ok())
-            .map(calculate_module_fuel)
-            .sum(),
-    )
+    fn part_two(masses: &Self::Input) -> Option<usize> {
+        Some(masses.iter().copied().map(calculate_module_fuel).sum())
+    }
 }
 
 #[cfg(test)]
@@ -1,5 +1,9 @@
-use advent_of_code::IntcodeComputer;
-advent_of_code::solution!(2);
+use advent_of_code::intcode::symbolic::SymbolicComputer;
+use advent_of_code::template::{ParseResult, Solution};
+use advent_of_code::{load_intcode_program, IntcodeComputer, IntcodeProgram};
+advent_of_code::solution!(2, Day02);
+
+pub struct Day02;
 
 const TARGET_OUTPUT: i128 = 19690720;
 
@@ -14,38 +18,84 @@ const VERB_DEFAULT: i128 = 2;
 const OUTPUT_REGISTER: usize = 0;
 const OUTPUT_FACTOR: i128 = 100;
 
-pub fn part_one(input: &str) -> Option<i128> {
-    let mut computer = IntcodeComputer::default();
+impl Solution for Day02 {
+    type Input = IntcodeProgram;
+    type PartOne = i128;
+    type PartTwo = i128;
+
+    fn parse(input: &str) -> ParseResult<Self::Input> {
+        Ok(load_intcode_program(input)?)
+    }
+
+    fn part_one(program: &Self::Input) -> Option<i128> {
+        let mut computer = IntcodeComputer::default();
+
+        run_with(&mut computer, program, DEFAULT_NOUN, VERB_DEFAULT)
+    }
+
+    fn part_two(program: &Self::Input) -> Option<i128> {
+        let mut computer = IntcodeComputer::default();
+
+        if let Some((noun, verb)) = solve_symbolically(program) {
+            if run_with(&mut computer, program, noun, verb)? == TARGET_OUTPUT {
+                return Some(OUTPUT_FACTOR * noun + verb);
+            }
+        }
+
+        // fall back to a search if the output does not depend linearly on noun and verb.
+        for noun in 0..=NOUN_MAX {
+            for verb in 0..=VERB_MAX {
+                if run_with(&mut computer, program, noun, verb)? == TARGET_OUTPUT {
+                    return Some(OUTPUT_FACTOR * noun + verb);
+                }
+            }
+        }
+
+        None
+    }
+}
 
-    computer.load_program_from_str(input);
+fn run_with(
+    computer: &mut IntcodeComputer,
+    program: &IntcodeProgram,
+    noun: i128,
+    verb: i128,
+) -> Option<i128> {
+    computer.load_program(program);
 
-    computer.set(NOUN_POSITION, DEFAULT_NOUN);
-    computer.set(VERB_POSITION, VERB_DEFAULT);
+    computer.set(NOUN_POSITION, noun);
+    computer.set(VERB_POSITION, verb);
 
     computer.run();
 
     computer.get(OUTPUT_REGISTER)
 }
 
-pub fn part_two(input: &str) -> Option<i128> {
-    let mut computer = IntcodeComputer::default();
+/// Runs the program with symbolic noun and verb and solves the output expression for the target.
+fn solve_symbolically(program: &IntcodeProgram) -> Option<(i128, i128)> {
+    let mut machine = SymbolicComputer::new(program);
 
-    for noun in 0..=NOUN_MAX {
-        for verb in 0..=VERB_MAX {
-            computer.load_program_from_str(input);
+    let noun = machine.variable("noun", 0..=NOUN_MAX);
+    let verb = machine.variable("verb", 0..=VERB_MAX);
 
-            computer.set(NOUN_POSITION, noun);
-            computer.set(VERB_POSITION, verb);
+    machine.set(NOUN_POSITION, noun.expr());
+    machine.set(VERB_POSITION, verb.expr());
 
-            computer.run();
+    let paths = machine.explore().ok()?;
+    let [path] = paths.as_slice() else {
+        return None;
+    };
 
-            if computer.get(OUTPUT_REGISTER)? == TARGET_OUTPUT {
-                return Some(OUTPUT_FACTOR * noun + verb);
-            }
-        }
-    }
+    let solution = path
+        .memory
+        .get(OUTPUT_REGISTER)?
+        .as_linear()?
+        .solve(TARGET_OUTPUT)?;
 
-    None
+    Some((
+        solution.get(&noun).copied().unwrap_or(0),
+        solution.get(&verb).copied().unwrap_or(0),
+    ))
 }
 
 #[cfg(test)]
@@ -62,10 +112,20 @@ mod tests {
     fn test_run_program(#[case] input: &str, #[case] expected: Vec<i128>) {
         let mut computer = IntcodeComputer::new();
 
-        computer.load_program_from_str(input);
+        computer.load_program_from_str(input).unwrap();
         computer.run();
 
         let result = computer.memory_snapshot();
         assert_eq!(result, expected);
     }
+
+    #[rstest]
+    #[case::linear("1,0,0,3,2,1,20,0,1,0,2,0,1,0,21,0,99,0,0,0,100000,18490715", 1205)]
+    #[case::non_linear("1,0,0,3,2,1,1,0,1,0,2,0,1,0,20,0,99,0,0,0,19690576", 795)]
+    fn test_part_two(#[case] input: &str, #[case] expected: i128) {
+        assert_eq!(
+            Day02::part_two(&Day02::parse(input).unwrap()),
+            Some(expected)
+        );
+    }
 }
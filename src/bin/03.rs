@@ -1,8 +1,11 @@
 use std::collections::HashSet;
 
+use advent_of_code::template::{ParseResult, Solution};
 use glam::IVec2;
 
-advent_of_code::solution!(3);
+advent_of_code::solution!(3, Day03);
+
+pub struct Day03;
 
 fn manhattan_distance(a: &IVec2) -> u32 {
     a.x.unsigned_abs() + a.y.unsigned_abs()
@@ -60,6 +63,12 @@ fn find_intersections(wire1: &[IVec2], wire2: &[IVec2]) -> HashSet<(u32, u32)> {
     intersections
 }
 
+fn parse_wires(input: &str) -> Option<(Vec<IVec2>, Vec<IVec2>)> {
+    let lines = input.trim().lines().take(2).collect::<Vec<_>>();
+
+    Some((parse_wire(lines[0])?, parse_wire(lines[1])?))
+}
+
 fn parse_wire(line: &str) -> Option<Vec<IVec2>> {
     let mut prev_position = IVec2::ZERO;
     let mut wire = Vec::from([prev_position]);
@@ -84,22 +93,26 @@ fn parse_wire(line: &str) -> Option<Vec<IVec2>> {
     Some(wire)
 }
 
-fn parse(input: &str) -> Option<(Vec<IVec2>, Vec<IVec2>)> {
-    let lines = input.trim().lines().take(2).collect::<Vec<_>>();
+impl Solution for Day03 {
+    type Input = Option<(Vec<IVec2>, Vec<IVec2>)>;
+    type PartOne = u32;
+    type PartTwo = u32;
 
-    Some((parse_wire(lines[0])?, parse_wire(lines[1])?))
-}
+    fn parse(input: &str) -> ParseResult<Self::Input> {
+        Ok(parse_wires(input))
+    }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let (wire1, wire2) = parse(input)?;
+    fn part_one(wires: &Self::Input) -> Option<u32> {
+        let (wire1, wire2) = wires.as_ref()?;
 
-    find_intersections(&wire1, &wire2).iter().map(|i| i.0).min()
-}
+        find_intersections(wire1, wire2).iter().map(|i| i.0).min()
+    }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let (wire1, wire2) = parse(input)?;
+    fn part_two(wires: &Self::Input) -> Option<u32> {
+        let (wire1, wire2) = wires.as_ref()?;
 
-    find_intersections(&wire1, &wire2).iter().map(|i| i.1).min()
+        find_intersections(wire1, wire2).iter().map(|i| i.1).min()
+    }
 }
 
 #[cfg(test)]
@@ -108,13 +121,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let input = Day03::parse(&advent_of_code::template::read_file("examples", DAY)).unwrap();
+        let result = Day03::part_one(&input);
         assert_eq!(result, Some(159));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let input = Day03::parse(&advent_of_code::template::read_file("examples", DAY)).unwrap();
+        let result = Day03::part_two(&input);
         assert_eq!(result, Some(610));
     }
 }
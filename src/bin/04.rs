@@ -1,4 +1,8 @@
-advent_of_code::solution!(4);
+use advent_of_code::template::{ParseResult, Solution};
+
+advent_of_code::solution!(4, Day04);
+
+pub struct Day04;
 
 fn is_valid_password(mut test: u32, part_of_larger_group: bool) -> bool {
     let mut prev_digit = 10; // No digit can be 10, ensuring the first comparison always fails if necessary.
@@ -35,31 +39,41 @@ fn is_valid_password(mut test: u32, part_of_larger_group: bool) -> bool {
     has_double || current_streak == 2 // Check has_double or if the last digits form a valid pair
 }
 
-fn parse(input: &str) -> Option<(u32, u32)> {
+fn parse_range(input: &str) -> Option<(u32, u32)> {
     let mut parts = input.trim().split('-');
     let min_password = parts.next()?.parse().ok()?;
     let max_password = parts.next()?.parse().ok()?;
     Some((min_password, max_password))
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let (min_password, max_password) = parse(input)?;
+impl Solution for Day04 {
+    type Input = Option<(u32, u32)>;
+    type PartOne = u32;
+    type PartTwo = u32;
 
-    (min_password..=max_password)
-        .filter(|&test| is_valid_password(test, true))
-        .count()
-        .try_into()
-        .ok()
-}
+    fn parse(input: &str) -> ParseResult<Self::Input> {
+        Ok(parse_range(input))
+    }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let (min_password, max_password) = parse(input)?;
+    fn part_one(range: &Self::Input) -> Option<u32> {
+        let (min_password, max_password) = (*range)?;
 
-    (min_password..=max_password)
-        .filter(|&test| is_valid_password(test, false))
-        .count()
-        .try_into()
-        .ok()
+        (min_password..=max_password)
+            .filter(|&test| is_valid_password(test, true))
+            .count()
+            .try_into()
+            .ok()
+    }
+
+    fn part_two(range: &Self::Input) -> Option<u32> {
+        let (min_password, max_password) = (*range)?;
+
+        (min_password..=max_password)
+            .filter(|&test| is_valid_password(test, false))
+            .count()
+            .try_into()
+            .ok()
+    }
 }
 
 #[cfg(test)]
@@ -68,13 +82,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let input = Day04::parse(&advent_of_code::template::read_file("examples", DAY)).unwrap();
+        let result = Day04::part_one(&input);
         assert_eq!(result, None);
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let input = Day04::parse(&advent_of_code::template::read_file("examples", DAY)).unwrap();
+        let result = Day04::part_two(&input);
         assert_eq!(result, None);
     }
 }
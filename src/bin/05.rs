@@ -1,29 +1,42 @@
-use advent_of_code::IntcodeComputer;
+use advent_of_code::template::{ParseResult, Solution};
+use advent_of_code::{load_intcode_program, IntcodeComputer, IntcodeProgram};
 
-advent_of_code::solution!(5);
+advent_of_code::solution!(5, Day05);
 
-pub fn part_one(input: &str) -> Option<i128> {
-    let mut computer = IntcodeComputer::default();
+pub struct Day05;
 
-    computer.set_input(1);
+impl Solution for Day05 {
+    type Input = IntcodeProgram;
+    type PartOne = i128;
+    type PartTwo = i128;
 
-    computer.load_program_from_str(input);
-    computer.run();
+    fn parse(input: &str) -> ParseResult<Self::Input> {
+        Ok(load_intcode_program(input)?)
+    }
 
-    // return the last value in output
-    computer.get_next_output()
-}
+    fn part_one(program: &Self::Input) -> Option<i128> {
+        let mut computer = IntcodeComputer::default();
 
-pub fn part_two(input: &str) -> Option<i128> {
-    let mut computer = IntcodeComputer::default();
+        computer.set_input(1);
 
-    computer.set_input(5);
+        computer.load_program(program);
+        computer.run();
 
-    computer.load_program_from_str(input);
-    computer.run();
+        // return the last value in output
+        computer.get_next_output()
+    }
 
-    // return the last value in output
-    computer.get_next_output()
+    fn part_two(program: &Self::Input) -> Option<i128> {
+        let mut computer = IntcodeComputer::default();
+
+        computer.set_input(5);
+
+        computer.load_program(program);
+        computer.run();
+
+        // return the last value in output
+        computer.get_next_output()
+    }
 }
 
 #[cfg(test)]
@@ -49,7 +62,7 @@ mod tests {
 
         computer.set_input(input);
 
-        computer.load_program_from_str(program);
+        computer.load_program_from_str(program).unwrap();
         computer.run();
 
         // return the last value in output
@@ -66,7 +79,9 @@ mod tests {
 
         computer.set_input(input);
 
-        computer.load_program_from_str(&advent_of_code::template::read_file("examples", DAY));
+        computer
+            .load_program_from_str(&advent_of_code::template::read_file("examples", DAY))
+            .unwrap();
         computer.run();
 
         // return the last value in output
@@ -1,9 +1,12 @@
+use advent_of_code::template::{ParseResult, Solution};
 use pathfinding::prelude::bfs;
 use petgraph::graph::{DiGraph, NodeIndex};
 use petgraph::visit::{VisitMap, Visitable};
 use std::collections::{HashMap, VecDeque};
 
-advent_of_code::solution!(6);
+advent_of_code::solution!(6, Day06);
+
+pub struct Day06;
 
 struct DirectedGraph {
     graph: DiGraph<String, ()>, // Use unit type for edge weights since they're not used
@@ -98,42 +101,53 @@ impl UndirectedGraph {
     }
 }
 
-fn parse_edge(line: &str) -> (&str, &str) {
-    line.split_once(')').unwrap()
+fn parse_edge(line: &str) -> ParseResult<(String, String)> {
+    let (parent, child) = line
+        .split_once(')')
+        .ok_or_else(|| format!("invalid orbit: {line:?}"))?;
+    Ok((parent.to_string(), child.to_string()))
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let mut graph = DirectedGraph::new();
-
-    for line in input.lines() {
-        let (parent, child) = parse_edge(line);
-        graph.add_node(parent);
-        graph.add_node(child);
+impl Solution for Day06 {
+    type Input = Vec<(String, String)>;
+    type PartOne = usize;
+    type PartTwo = usize;
 
-        graph.add_edge(parent, child);
+    fn parse(input: &str) -> ParseResult<Self::Input> {
+        input.lines().map(parse_edge).collect()
     }
 
-    let depths = graph.calculate_depths("COM");
+    fn part_one(edges: &Self::Input) -> Option<usize> {
+        let mut graph = DirectedGraph::new();
 
-    let mut orbits = 0;
-    for (_, depth) in depths {
-        orbits += depth;
-    }
+        for (parent, child) in edges {
+            graph.add_node(parent);
+            graph.add_node(child);
 
-    Some(orbits)
-}
+            graph.add_edge(parent, child);
+        }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let mut graph = UndirectedGraph::new();
+        let depths = graph.calculate_depths("COM");
 
-    for line in input.lines() {
-        let (parent, child) = parse_edge(line);
-        graph.add_edge(parent, child);
+        let mut orbits = 0;
+        for (_, depth) in depths {
+            orbits += depth;
+        }
+
+        Some(orbits)
     }
 
-    graph
-        .shortest_path("YOU", "SAN")
-        .map(|distance| distance.len() - 3)
+    fn part_two(edges: &Self::Input) -> Option<usize> {
+        let mut graph = UndirectedGraph::new();
+
+        for (parent, child) in edges {
+            graph.add_edge(parent, child);
+        }
+
+        graph
+            .shortest_path("YOU", "SAN")
+            .map(|distance| distance.len() - 3)
+    }
 }
 
 #[cfg(test)]
@@ -142,15 +156,18 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let input = Day06::parse(&advent_of_code::template::read_file("examples", DAY)).unwrap();
+        let result = Day06::part_one(&input);
         assert_eq!(result, Some(42));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file_part(
+        let input = Day06::parse(&advent_of_code::template::read_file_part(
             "examples", DAY, 2,
-        ));
+        ))
+        .unwrap();
+        let result = Day06::part_two(&input);
         assert_eq!(result, Some(4));
     }
 }
@@ -1,11 +1,31 @@
-use advent_of_code::{parse_intcode_program, IntcodeComputer, IntcodeProgram};
+use advent_of_code::intcode::asynchronous::{block_on, join_all, AsyncIntcodeComputer, Channel};
+use advent_of_code::template::{ParseResult, Solution};
+use advent_of_code::{load_intcode_program, IntcodeComputer, IntcodeProgram};
 use itertools::Itertools;
 
-advent_of_code::solution!(7);
+advent_of_code::solution!(7, Day07);
 
-pub fn part_one(input: &str) -> Option<i128> {
-    let program: IntcodeProgram = parse_intcode_program(input)?;
+pub struct Day07;
 
+impl Solution for Day07 {
+    type Input = IntcodeProgram;
+    type PartOne = i128;
+    type PartTwo = i128;
+
+    fn parse(input: &str) -> ParseResult<Self::Input> {
+        Ok(load_intcode_program(input)?)
+    }
+
+    fn part_one(program: &Self::Input) -> Option<i128> {
+        max_thruster_signal(program)
+    }
+
+    fn part_two(program: &Self::Input) -> Option<i128> {
+        max_feedback_signal(program)
+    }
+}
+
+fn max_thruster_signal(program: &IntcodeProgram) -> Option<i128> {
     let mut max_thruster_signal = 0;
 
     let phase_settings = [0, 1, 2, 3, 4];
@@ -18,7 +38,7 @@ pub fn part_one(input: &str) -> Option<i128> {
             computer.set_input(*amplifier);
             computer.set_input(input_signal);
 
-            computer.load_program(&program);
+            computer.load_program(program);
             computer.run();
 
             // return the last value in output
@@ -33,43 +53,34 @@ pub fn part_one(input: &str) -> Option<i128> {
     Some(max_thruster_signal)
 }
 
-pub fn part_two(input: &str) -> Option<i128> {
-    let program: IntcodeProgram = parse_intcode_program(input)?;
-
+fn max_feedback_signal(program: &IntcodeProgram) -> Option<i128> {
     let mut max_thruster_signal = 0;
 
     let phase_settings = [5, 6, 7, 8, 9];
     for p in phase_settings.iter().permutations(phase_settings.len()) {
-        let mut input_signal = 0;
-        let mut amplifiers = Vec::new();
-
-        for amplifier in p {
+        // channel `i` feeds amplifier `i`, the last amplifier feeds back into the first.
+        let channels: Vec<Channel> = p
+            .iter()
+            .map(|phase| {
+                let channel = Channel::new();
+                channel.send(**phase);
+                channel
+            })
+            .collect();
+
+        channels[0].send(0);
+
+        let amplifiers = (0..channels.len()).map(|i| {
             let mut computer = IntcodeComputer::default();
+            computer.load_program(program);
 
-            computer.load_program(&program);
+            let output = &channels[(i + 1) % channels.len()];
+            AsyncIntcodeComputer::new(computer, channels[i].clone(), output.clone()).run()
+        });
 
-            computer.set_input(*amplifier);
-
-            amplifiers.push(computer);
-        }
+        block_on(join_all(amplifiers))?;
 
-        loop {
-            for amplifier in amplifiers.iter_mut() {
-                amplifier.set_input(input_signal);
-
-                while amplifier.is_running() && amplifier.get_output().is_empty() {
-                    amplifier.tick();
-                }
-
-                if let Some(output) = amplifier.get_next_output() {
-                    input_signal = output;
-                }
-            }
-
-            if !amplifiers[0].is_running() {
-                break;
-            }
-        }
+        let input_signal = channels[0].try_recv()?;
 
         if input_signal > max_thruster_signal {
             max_thruster_signal = input_signal;
@@ -85,15 +96,18 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let input = Day07::parse(&advent_of_code::template::read_file("examples", DAY)).unwrap();
+        let result = Day07::part_one(&input);
         assert_eq!(result, Some(43210));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file_part(
+        let input = Day07::parse(&advent_of_code::template::read_file_part(
             "examples", DAY, 2,
-        ));
+        ))
+        .unwrap();
+        let result = Day07::part_two(&input);
         assert_eq!(result, Some(139629729));
     }
 }
@@ -1,16 +1,32 @@
-advent_of_code::solution!(8);
-
-fn parse(input: &str) -> Vec<u32> {
-    input
-        .trim()
-        .chars()
-        .filter_map(|c| c.to_digit(10))
-        .collect()
-}
+use advent_of_code::template::{ParseResult, Solution};
+
+advent_of_code::solution!(8, Day08);
+
+pub struct Day08;
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let data = parse(input);
+impl Solution for Day08 {
+    type Input = Vec<u32>;
+    type PartOne = usize;
+    type PartTwo = String;
 
+    fn parse(input: &str) -> ParseResult<Self::Input> {
+        Ok(input
+            .trim()
+            .chars()
+            .filter_map(|c| c.to_digit(10))
+            .collect())
+    }
+
+    fn part_one(data: &Self::Input) -> Option<usize> {
+        checksum(data)
+    }
+
+    fn part_two(data: &Self::Input) -> Option<String> {
+        render(data)
+    }
+}
+
+fn checksum(data: &[u32]) -> Option<usize> {
     let mut zeros = usize::MAX;
     let mut checksum = 0;
 
@@ -29,9 +45,7 @@ pub fn part_one(input: &str) -> Option<usize> {
     Some(checksum)
 }
 
-pub fn part_two(input: &str) -> Option<String> {
-    let data = parse(input);
-
+fn render(data: &[u32]) -> Option<String> {
     let layers = data.len() / 150;
     let mut image = Vec::new();
 
@@ -1,29 +1,42 @@
-use advent_of_code::IntcodeComputer;
+use advent_of_code::template::{ParseResult, Solution};
+use advent_of_code::{load_intcode_program, IntcodeComputer, IntcodeProgram};
 
-advent_of_code::solution!(9);
+advent_of_code::solution!(9, Day09);
 
-pub fn part_one(input: &str) -> Option<i128> {
-    let mut computer = IntcodeComputer::default();
+pub struct Day09;
 
-    computer.set_input(1);
+impl Solution for Day09 {
+    type Input = IntcodeProgram;
+    type PartOne = i128;
+    type PartTwo = i128;
 
-    computer.load_program_from_str(input);
-    computer.run();
+    fn parse(input: &str) -> ParseResult<Self::Input> {
+        Ok(load_intcode_program(input)?)
+    }
 
-    // return the last value in output
-    computer.get_next_output()
-}
+    fn part_one(program: &Self::Input) -> Option<i128> {
+        let mut computer = IntcodeComputer::default();
 
-pub fn part_two(input: &str) -> Option<i128> {
-    let mut computer = IntcodeComputer::default();
+        computer.set_input(1);
 
-    computer.set_input(2);
+        computer.load_program(program);
+        computer.run();
 
-    computer.load_program_from_str(input);
-    computer.run();
+        // return the last value in output
+        computer.get_next_output()
+    }
 
-    // return the last value in output
-    computer.get_next_output()
+    fn part_two(program: &Self::Input) -> Option<i128> {
+        let mut computer = IntcodeComputer::default();
+
+        computer.set_input(2);
+
+        computer.load_program(program);
+        computer.run();
+
+        // return the last value in output
+        computer.get_next_output()
+    }
 }
 
 #[cfg(test)]
@@ -38,7 +51,7 @@ mod tests {
     fn test_intcode_changes(#[case] program: &str, #[case] expected: i128) {
         let mut computer = IntcodeComputer::default();
 
-        computer.load_program_from_str(program);
+        computer.load_program_from_str(program).unwrap();
         computer.run();
 
         // return the last value in output
@@ -3,11 +3,14 @@ use std::{
     hash::{Hash, Hasher},
 };
 
+use advent_of_code::template::{ParseResult, Solution};
 use glam::Vec2;
 
-advent_of_code::solution!(10);
+advent_of_code::solution!(10, Day10);
 
-struct Map {
+pub struct Day10;
+
+pub struct Map {
     asteroids: Vec<Vec2>,
     width: usize,
     height: usize,
@@ -45,7 +48,7 @@ impl Hash for Vec2Key {
     }
 }
 
-fn parse_map(input: &str) -> Option<Map> {
+fn parse_map(input: &str) -> Map {
     let mut map = Map::new();
 
     for (y, line) in input.lines().enumerate() {
@@ -64,7 +67,7 @@ fn parse_map(input: &str) -> Option<Map> {
         }
     }
 
-    Some(map)
+    map
 }
 
 fn find_best_asteroid(map: &Map) -> Option<(Vec2, usize)> {
@@ -92,18 +95,28 @@ fn find_best_asteroid(map: &Map) -> Option<(Vec2, usize)> {
     Some((best_asteroid?, max_asteroids_detected))
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let map = parse_map(input)?;
+impl Solution for Day10 {
+    type Input = Map;
+    type PartOne = usize;
+    type PartTwo = u32;
 
-    let (_, max_asteroids_detected) = find_best_asteroid(&map)?;
+    fn parse(input: &str) -> ParseResult<Self::Input> {
+        Ok(parse_map(input))
+    }
 
-    Some(max_asteroids_detected)
-}
+    fn part_one(map: &Self::Input) -> Option<usize> {
+        let (_, max_asteroids_detected) = find_best_asteroid(map)?;
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let map = parse_map(input)?;
+        Some(max_asteroids_detected)
+    }
+
+    fn part_two(map: &Self::Input) -> Option<u32> {
+        vaporize(map)
+    }
+}
 
-    let (best_asteroid, _) = find_best_asteroid(&map)?;
+fn vaporize(map: &Map) -> Option<u32> {
+    let (best_asteroid, _) = find_best_asteroid(map)?;
 
     let mut angles = HashMap::new();
 
@@ -116,9 +129,7 @@ pub fn part_two(input: &str) -> Option<u32> {
         let angle_key = Vec2Key(angle);
         let distance = (*other - best_asteroid).length_squared();
 
-        let entry = angles
-            .entry(angle_key)
-            .or_insert((Vec2::ZERO, std::f32::MAX));
+        let entry = angles.entry(angle_key).or_insert((Vec2::ZERO, f32::MAX));
         if distance < entry.1 {
             *entry = (*other, distance);
         }
@@ -171,13 +182,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let input = Day10::parse(&advent_of_code::template::read_file("examples", DAY)).unwrap();
+        let result = Day10::part_one(&input);
         assert_eq!(result, Some(210));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let input = Day10::parse(&advent_of_code::template::read_file("examples", DAY)).unwrap();
+        let result = Day10::part_two(&input);
         assert_eq!(result, Some(802));
     }
 }
@@ -1,8 +1,11 @@
-use advent_of_code::IntcodeComputer;
+use advent_of_code::template::{ParseResult, Solution};
+use advent_of_code::{load_intcode_program, IntcodeComputer, IntcodeProgram};
 use glam::IVec2;
 use std::collections::HashMap;
 
-advent_of_code::solution!(11);
+advent_of_code::solution!(11, Day11);
+
+pub struct Day11;
 
 struct Robot {
     position: IVec2,
@@ -12,9 +15,9 @@ struct Robot {
 }
 
 impl Robot {
-    fn new(input: &str) -> Self {
+    fn new(program: &IntcodeProgram) -> Self {
         let mut brain = IntcodeComputer::new();
-        brain.load_program_from_str(input);
+        brain.load_program(program);
 
         Self {
             position: IVec2::ZERO,
@@ -59,16 +62,30 @@ impl Robot {
     }
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let mut robot = Robot::new(input);
+impl Solution for Day11 {
+    type Input = IntcodeProgram;
+    type PartOne = usize;
+    type PartTwo = String;
 
-    robot.run();
+    fn parse(input: &str) -> ParseResult<Self::Input> {
+        Ok(load_intcode_program(input)?)
+    }
+
+    fn part_one(program: &Self::Input) -> Option<usize> {
+        let mut robot = Robot::new(program);
 
-    Some(robot.panels.len())
+        robot.run();
+
+        Some(robot.panels.len())
+    }
+
+    fn part_two(program: &Self::Input) -> Option<String> {
+        Some(paint_registration(program))
+    }
 }
 
-pub fn part_two(input: &str) -> Option<String> {
-    let mut robot = Robot::new(input);
+fn paint_registration(program: &IntcodeProgram) -> String {
+    let mut robot = Robot::new(program);
 
     robot.panels.insert(IVec2::ZERO, 1);
 
@@ -91,7 +108,7 @@ pub fn part_two(input: &str) -> Option<String> {
         result.push('\n');
     }
 
-    Some(result)
+    result
 }
 
 #[cfg(test)]
@@ -1,10 +1,13 @@
+use advent_of_code::template::{ParseResult, Solution};
 use glam::IVec3;
 use std::cmp::Ordering;
 
-advent_of_code::solution!(12);
+advent_of_code::solution!(12, Day12);
+
+pub struct Day12;
 
 #[derive(Debug, Clone, Copy)]
-struct Moon {
+pub struct Moon {
     position: IVec3,
     velocity: IVec3,
 }
@@ -56,10 +59,6 @@ fn parse_moon(line: &str) -> Option<Moon> {
     Some(moon)
 }
 
-fn parse(input: &str) -> Vec<Moon> {
-    input.lines().filter_map(parse_moon).collect()
-}
-
 fn simulate(moons: &mut [Moon], total_steps: usize) -> i32 {
     let mut steps = 0;
 
@@ -149,20 +148,28 @@ fn lcm3(a: u64, b: u64, c: u64) -> u64 {
     lcm(a, lcm(b, c))
 }
 
-pub fn part_one(input: &str) -> Option<i32> {
-    let mut moons = parse(input);
+impl Solution for Day12 {
+    type Input = Vec<Moon>;
+    type PartOne = i32;
+    type PartTwo = u64;
 
-    Some(simulate(&mut moons, 1000))
-}
+    fn parse(input: &str) -> ParseResult<Self::Input> {
+        Ok(input.lines().filter_map(parse_moon).collect())
+    }
+
+    fn part_one(moons: &Self::Input) -> Option<i32> {
+        let mut moons = moons.clone();
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let moons = parse(input);
+        Some(simulate(&mut moons, 1000))
+    }
 
-    let x = find_steps_axis(moons.iter().map(|m| m.position.x).collect());
-    let y = find_steps_axis(moons.iter().map(|m| m.position.y).collect());
-    let z = find_steps_axis(moons.iter().map(|m| m.position.z).collect());
+    fn part_two(moons: &Self::Input) -> Option<u64> {
+        let x = find_steps_axis(moons.iter().map(|m| m.position.x).collect());
+        let y = find_steps_axis(moons.iter().map(|m| m.position.y).collect());
+        let z = find_steps_axis(moons.iter().map(|m| m.position.z).collect());
 
-    Some(lcm3(x, y, z))
+        Some(lcm3(x, y, z))
+    }
 }
 
 #[cfg(test)]
@@ -172,7 +179,7 @@ mod tests {
     #[test]
     fn test_part_one() {
         let input = &advent_of_code::template::read_file("examples", DAY);
-        let mut moons = parse(input);
+        let mut moons = Day12::parse(input).unwrap();
 
         let result = simulate(&mut moons, 10);
 
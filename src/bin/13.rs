@@ -1,9 +1,12 @@
 use glam::IVec2;
 use std::{cmp::Ordering, collections::HashMap};
 
-use advent_of_code::IntcodeComputer;
+use advent_of_code::template::{ParseResult, Solution};
+use advent_of_code::{load_intcode_program, IntcodeComputer, IntcodeProgram};
 
-advent_of_code::solution!(13);
+advent_of_code::solution!(13, Day13);
+
+pub struct Day13;
 
 const QUARTER_SLOT: usize = 0;
 const QUARTERS: i128 = 2;
@@ -65,10 +68,28 @@ fn draw_screen(screen: &HashMap<IVec2, i128>) {
     println!();
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
+impl Solution for Day13 {
+    type Input = IntcodeProgram;
+    type PartOne = usize;
+    type PartTwo = i128;
+
+    fn parse(input: &str) -> ParseResult<Self::Input> {
+        Ok(load_intcode_program(input)?)
+    }
+
+    fn part_one(program: &Self::Input) -> Option<usize> {
+        count_blocks(program)
+    }
+
+    fn part_two(program: &Self::Input) -> Option<i128> {
+        play(program)
+    }
+}
+
+fn count_blocks(program: &IntcodeProgram) -> Option<usize> {
     let mut arcade_cabinet = IntcodeComputer::new();
 
-    arcade_cabinet.load_program_from_str(input);
+    arcade_cabinet.load_program(program);
 
     let mut screen = HashMap::new();
 
@@ -85,10 +106,10 @@ pub fn part_one(input: &str) -> Option<usize> {
     Some(blocks_remaining(&screen))
 }
 
-pub fn part_two(input: &str) -> Option<i128> {
+fn play(program: &IntcodeProgram) -> Option<i128> {
     let mut arcade_cabinet = IntcodeComputer::new();
 
-    arcade_cabinet.load_program_from_str(input);
+    arcade_cabinet.load_program(program);
     arcade_cabinet.set(QUARTER_SLOT, QUARTERS);
 
     let mut screen = HashMap::new();
@@ -1,6 +1,10 @@
 use std::collections::{HashMap, VecDeque};
 
-advent_of_code::solution!(14);
+use advent_of_code::template::{ParseResult, Solution};
+
+advent_of_code::solution!(14, Day14);
+
+pub struct Day14;
 
 #[derive(Debug)]
 struct Chemical {
@@ -9,7 +13,7 @@ struct Chemical {
 }
 
 #[derive(Debug)]
-struct Reaction {
+pub struct Reaction {
     inputs: Vec<Chemical>,
     output: Chemical,
 }
@@ -90,44 +94,51 @@ fn calculate_ore(reactions: &[Reaction], fuel: u64) -> u64 {
     ore
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let reactions = parse_input(input);
+impl Solution for Day14 {
+    type Input = Vec<Reaction>;
+    type PartOne = u64;
+    type PartTwo = u64;
 
-    let ore = calculate_ore(&reactions, 1);
+    fn parse(input: &str) -> ParseResult<Self::Input> {
+        Ok(parse_input(input))
+    }
 
-    Some(ore)
-}
+    fn part_one(reactions: &Self::Input) -> Option<u64> {
+        let ore = calculate_ore(reactions, 1);
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let reactions = parse_input(input);
-    let one_fuel = calculate_ore(&reactions, 1);
+        Some(ore)
+    }
 
-    let mut try_fuel = 1_000_000_000_000 / one_fuel;
+    fn part_two(reactions: &Self::Input) -> Option<u64> {
+        let one_fuel = calculate_ore(reactions, 1);
 
-    loop {
-        let ore = calculate_ore(&reactions, try_fuel);
-        if ore > 1_000_000_000_000 {
-            break;
-        }
+        let mut try_fuel = 1_000_000_000_000 / one_fuel;
 
-        try_fuel *= 2;
-    }
+        loop {
+            let ore = calculate_ore(reactions, try_fuel);
+            if ore > 1_000_000_000_000 {
+                break;
+            }
+
+            try_fuel *= 2;
+        }
 
-    let mut lower = try_fuel / 2;
-    let mut upper = try_fuel;
+        let mut lower = try_fuel / 2;
+        let mut upper = try_fuel;
 
-    while lower < upper {
-        let mid = (lower + upper) / 2;
+        while lower < upper {
+            let mid = (lower + upper) / 2;
 
-        let ore = calculate_ore(&reactions, mid);
-        if ore > 1_000_000_000_000 {
-            upper = mid;
-        } else {
-            lower = mid + 1;
+            let ore = calculate_ore(reactions, mid);
+            if ore > 1_000_000_000_000 {
+                upper = mid;
+            } else {
+                lower = mid + 1;
+            }
         }
-    }
 
-    Some(lower - 1)
+        Some(lower - 1)
+    }
 }
 
 #[cfg(test)]
@@ -136,13 +147,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let input = Day14::parse(&advent_of_code::template::read_file("examples", DAY)).unwrap();
+        let result = Day14::part_one(&input);
         assert_eq!(result, Some(13312));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let input = Day14::parse(&advent_of_code::template::read_file("examples", DAY)).unwrap();
+        let result = Day14::part_two(&input);
         assert_eq!(result, Some(82892753));
     }
 }
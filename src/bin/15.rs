@@ -1,9 +1,12 @@
 use glam::IVec2;
 use std::collections::{HashMap, VecDeque};
 
-use advent_of_code::IntcodeComputer;
+use advent_of_code::template::{ParseResult, Solution};
+use advent_of_code::{load_intcode_program, IntcodeComputer, IntcodeProgram};
 
-advent_of_code::solution!(15);
+advent_of_code::solution!(15, Day15);
+
+pub struct Day15;
 
 const MOVE_NORTH: i128 = 1;
 const MOVE_SOUTH: i128 = 2;
@@ -41,9 +44,27 @@ fn explore(computer: IntcodeComputer, map: &mut HashMap<IVec2, i128>, position:
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
+impl Solution for Day15 {
+    type Input = IntcodeProgram;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> ParseResult<Self::Input> {
+        Ok(load_intcode_program(input)?)
+    }
+
+    fn part_one(program: &Self::Input) -> Option<u32> {
+        distance_to_oxygen(program)
+    }
+
+    fn part_two(program: &Self::Input) -> Option<u32> {
+        Some(time_to_fill(program))
+    }
+}
+
+fn distance_to_oxygen(program: &IntcodeProgram) -> Option<u32> {
     let mut computer = IntcodeComputer::new();
-    computer.load_program_from_str(input);
+    computer.load_program(program);
 
     let mut map = HashMap::new();
 
@@ -98,9 +119,9 @@ pub fn part_one(input: &str) -> Option<u32> {
     None
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+fn time_to_fill(program: &IntcodeProgram) -> u32 {
     let mut computer = IntcodeComputer::new();
-    computer.load_program_from_str(input);
+    computer.load_program(program);
 
     let mut map = HashMap::new();
 
@@ -139,7 +160,7 @@ pub fn part_two(input: &str) -> Option<u32> {
         }
     }
 
-    Some(max_distance as u32)
+    max_distance as u32
 }
 
 #[cfg(test)]
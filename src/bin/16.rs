@@ -1,12 +1,8 @@
-advent_of_code::solution!(16);
-
-fn parse_input(input: &str) -> Vec<u32> {
-    input
-        .trim()
-        .chars()
-        .map(|c| c.to_digit(10).unwrap())
-        .collect()
-}
+use advent_of_code::template::{ParseResult, Solution};
+
+advent_of_code::solution!(16, Day16);
+
+pub struct Day16;
 
 fn phase(input: &[u32]) -> Vec<u32> {
     let mut output = Vec::new();
@@ -28,43 +24,58 @@ fn phase(input: &[u32]) -> Vec<u32> {
     output
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let mut input = parse_input(input);
+impl Solution for Day16 {
+    type Input = Vec<u32>;
+    type PartOne = u32;
+    type PartTwo = u32;
 
-    for _ in 0..100 {
-        input = phase(&input);
+    fn parse(input: &str) -> ParseResult<Self::Input> {
+        input
+            .trim()
+            .chars()
+            .map(|c| {
+                c.to_digit(10)
+                    .ok_or_else(|| format!("invalid digit: {c:?}").into())
+            })
+            .collect()
     }
 
-    Some(input.iter().take(8).fold(0, |acc, &n| acc * 10 + n))
-}
+    fn part_one(signal: &Self::Input) -> Option<u32> {
+        let mut input = signal.clone();
+
+        for _ in 0..100 {
+            input = phase(&input);
+        }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let mut input = parse_input(input);
+        Some(input.iter().take(8).fold(0, |acc, &n| acc * 10 + n))
+    }
 
-    let offset = input.iter().take(7).fold(0, |acc, &n| acc * 10 + n) as usize;
+    fn part_two(signal: &Self::Input) -> Option<u32> {
+        let offset = signal.iter().take(7).fold(0, |acc, &n| acc * 10 + n) as usize;
 
-    input = input
-        .iter()
-        .cycle()
-        .take(input.len() * 10_000)
-        .cloned()
-        .collect();
+        let mut input: Vec<u32> = signal
+            .iter()
+            .cycle()
+            .take(signal.len() * 10_000)
+            .cloned()
+            .collect();
 
-    for _ in 0..100 {
-        let mut sum = 0;
-        for n in input.iter_mut().rev() {
-            sum = (sum + *n) % 10;
-            *n = sum;
+        for _ in 0..100 {
+            let mut sum = 0;
+            for n in input.iter_mut().rev() {
+                sum = (sum + *n) % 10;
+                *n = sum;
+            }
         }
-    }
 
-    Some(
-        input
-            .iter()
-            .skip(offset)
-            .take(8)
-            .fold(0, |acc, &n| acc * 10 + n),
-    )
+        Some(
+            input
+                .iter()
+                .skip(offset)
+                .take(8)
+                .fold(0, |acc, &n| acc * 10 + n),
+        )
+    }
 }
 
 #[cfg(test)]
@@ -77,7 +88,7 @@ mod tests {
     #[case("19617804207202209144916044189917", 73745418)]
     #[case("69317163492948606335995924319873", 52432133)]
     fn test_part_one(#[case] input: &str, #[case] expected: u32) {
-        let result = part_one(input);
+        let result = Day16::part_one(&Day16::parse(input).unwrap());
         assert_eq!(result, Some(expected));
     }
 
@@ -86,7 +97,7 @@ mod tests {
     #[case("02935109699940807407585447034323", 78725270)]
     #[case("03081770884921959731165446850517", 53553731)]
     fn test_part_two(#[case] input: &str, #[case] expected: u32) {
-        let result = part_two(input);
+        let result = Day16::part_two(&Day16::parse(input).unwrap());
         assert_eq!(result, Some(expected));
     }
 }
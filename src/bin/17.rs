@@ -1,7 +1,10 @@
-use advent_of_code::IntcodeComputer;
+use advent_of_code::template::{ParseResult, Solution};
+use advent_of_code::{load_intcode_program, IntcodeComputer, IntcodeProgram};
 use glam::IVec2;
 
-advent_of_code::solution!(17);
+advent_of_code::solution!(17, Day17);
+
+pub struct Day17;
 
 struct ShipExterior {
     scaffolding: Vec<IVec2>,
@@ -112,9 +115,28 @@ fn turn_left(dir: IVec2) -> IVec2 {
 fn turn_right(dir: IVec2) -> IVec2 {
     IVec2::new(-dir.y, dir.x)
 }
-pub fn part_one(input: &str) -> Option<u32> {
+
+impl Solution for Day17 {
+    type Input = IntcodeProgram;
+    type PartOne = u32;
+    type PartTwo = i128;
+
+    fn parse(input: &str) -> ParseResult<Self::Input> {
+        Ok(load_intcode_program(input)?)
+    }
+
+    fn part_one(program: &Self::Input) -> Option<u32> {
+        Some(alignment_parameters(program))
+    }
+
+    fn part_two(program: &Self::Input) -> Option<i128> {
+        collect_dust(program)
+    }
+}
+
+fn alignment_parameters(program: &IntcodeProgram) -> u32 {
     let mut computer = IntcodeComputer::new();
-    computer.load_program_from_str(input);
+    computer.load_program(program);
 
     let exterior = read_ship_exterior_from_computer(&mut computer);
 
@@ -128,12 +150,12 @@ pub fn part_one(input: &str) -> Option<u32> {
         }
     }
 
-    Some(sum as u32)
+    sum as u32
 }
 
-pub fn part_two(input: &str) -> Option<i128> {
+fn collect_dust(program: &IntcodeProgram) -> Option<i128> {
     let mut computer = IntcodeComputer::new();
-    computer.load_program_from_str(input);
+    computer.load_program(program);
 
     computer.set(0, 2);
 
@@ -3,9 +3,12 @@ use std::{
     collections::{BTreeSet, BinaryHeap, HashMap, HashSet, VecDeque},
 };
 
+use advent_of_code::template::{ParseResult, Solution};
 use glam::IVec2;
 
-advent_of_code::solution!(18);
+advent_of_code::solution!(18, Day18);
+
+pub struct Day18;
 
 fn neighbors(pos: IVec2) -> Vec<IVec2> {
     vec![
@@ -17,7 +20,7 @@ fn neighbors(pos: IVec2) -> Vec<IVec2> {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
-enum Tile {
+pub enum Tile {
     Wall,
     Empty,
     Node(char),
@@ -165,7 +168,7 @@ fn search(graph: HashMap<char, HashMap<char, usize>>, start: char) -> usize {
 
             let distances_entry = distances
                 .entry((next_node, next_keys.clone()))
-                .or_insert(usize::max_value());
+                .or_insert(usize::MAX);
 
             if next_steps < *distances_entry {
                 *distances_entry = next_steps;
@@ -181,7 +184,7 @@ fn search(graph: HashMap<char, HashMap<char, usize>>, start: char) -> usize {
         }
     }
     // no path found
-    usize::max_value()
+    usize::MAX
 }
 
 fn search_keys(
@@ -192,7 +195,7 @@ fn search_keys(
     // dist[node] = current shortest distance from `start` to `node`
     let mut dist = HashMap::new();
     for &key in graph.keys() {
-        dist.insert(key, usize::max_value());
+        dist.insert(key, usize::MAX);
     }
 
     let mut heap = BinaryHeap::new();
@@ -319,7 +322,7 @@ fn search_four(graph: HashMap<char, HashMap<char, usize>>) -> usize {
 
                 let distances_entry = distances
                     .entry((next_robots, next_keys.clone()))
-                    .or_insert(usize::max_value());
+                    .or_insert(usize::MAX);
 
                 if next_steps < *distances_entry {
                     *distances_entry = next_steps;
@@ -335,7 +338,7 @@ fn search_four(graph: HashMap<char, HashMap<char, usize>>) -> usize {
         }
     }
     // no path found
-    usize::max_value()
+    usize::MAX
 }
 
 #[derive(PartialEq, Eq)]
@@ -360,19 +363,28 @@ impl PartialOrd for FourState {
     }
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let grid = parse_grid(input);
-    let graph = graph(&grid);
+impl Solution for Day18 {
+    type Input = HashMap<IVec2, Tile>;
+    type PartOne = usize;
+    type PartTwo = usize;
 
-    Some(search(graph, '@'))
-}
+    fn parse(input: &str) -> ParseResult<Self::Input> {
+        Ok(parse_grid(input))
+    }
+
+    fn part_one(grid: &Self::Input) -> Option<usize> {
+        let graph = graph(grid);
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let mut grid = parse_grid(input);
-    four_robots(&mut grid);
-    let graph = graph(&grid);
+        Some(search(graph, '@'))
+    }
+
+    fn part_two(grid: &Self::Input) -> Option<usize> {
+        let mut grid = grid.clone();
+        four_robots(&mut grid);
+        let graph = graph(&grid);
 
-    Some(search_four(graph))
+        Some(search_four(graph))
+    }
 }
 
 #[cfg(test)]
@@ -381,15 +393,18 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let input = Day18::parse(&advent_of_code::template::read_file("examples", DAY)).unwrap();
+        let result = Day18::part_one(&input);
         assert_eq!(result, Some(136));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file_part(
+        let input = Day18::parse(&advent_of_code::template::read_file_part(
             "examples", DAY, 2,
-        ));
+        ))
+        .unwrap();
+        let result = Day18::part_two(&input);
         assert_eq!(result, Some(8));
     }
 }
@@ -1,6 +1,9 @@
-use advent_of_code::IntcodeComputer;
+use advent_of_code::template::{ParseResult, Solution};
+use advent_of_code::{load_intcode_program, IntcodeComputer, IntcodeProgram};
 
-advent_of_code::solution!(19);
+advent_of_code::solution!(19, Day19);
+
+pub struct Day19;
 
 fn is_point_in_beam(x: i128, y: i128, mut computer: IntcodeComputer) -> bool {
     computer.set_input(x);
@@ -17,9 +20,27 @@ fn is_point_in_beam(x: i128, y: i128, mut computer: IntcodeComputer) -> bool {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
+impl Solution for Day19 {
+    type Input = IntcodeProgram;
+    type PartOne = u32;
+    type PartTwo = i128;
+
+    fn parse(input: &str) -> ParseResult<Self::Input> {
+        Ok(load_intcode_program(input)?)
+    }
+
+    fn part_one(program: &Self::Input) -> Option<u32> {
+        Some(count_beam_points(program))
+    }
+
+    fn part_two(program: &Self::Input) -> Option<i128> {
+        Some(find_square(program))
+    }
+}
+
+fn count_beam_points(program: &IntcodeProgram) -> u32 {
     let mut computer = IntcodeComputer::new();
-    computer.load_program_from_str(input);
+    computer.load_program(program);
 
     let mut in_beam = 0;
 
@@ -31,12 +52,12 @@ pub fn part_one(input: &str) -> Option<u32> {
         }
     }
 
-    Some(in_beam)
+    in_beam
 }
 
-pub fn part_two(input: &str) -> Option<i128> {
+fn find_square(program: &IntcodeProgram) -> i128 {
     let mut computer = IntcodeComputer::new();
-    computer.load_program_from_str(input);
+    computer.load_program(program);
 
     let mut x = 100;
     let mut y = 300;
@@ -50,7 +71,7 @@ pub fn part_two(input: &str) -> Option<i128> {
             if is_point_in_beam(x, y + 99, computer.clone())
                 && is_point_in_beam(x + 99, y + 99, computer.clone())
             {
-                return Some(x * 10_000 + y);
+                return x * 10_000 + y;
             }
             x += 1;
         }
@@ -2,10 +2,14 @@ use glam::IVec2;
 use pathfinding::prelude::bfs;
 use std::{collections::HashMap, hash::Hash};
 
-advent_of_code::solution!(20);
+use advent_of_code::template::{ParseResult, Solution};
+
+advent_of_code::solution!(20, Day20);
+
+pub struct Day20;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-enum Tile {
+pub enum Tile {
     Empty,
     Portal(IVec2, String),
 }
@@ -53,149 +57,155 @@ fn parse_map_with_portals(input: &str) -> HashMap<IVec2, Tile> {
     map
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let map = parse_map_with_portals(input);
+impl Solution for Day20 {
+    type Input = HashMap<IVec2, Tile>;
+    type PartOne = u32;
+    type PartTwo = u32;
 
-    let start = map
-        .iter()
-        .find(|(_, t)| {
-            if let Tile::Portal(_, p) = t {
-                p == "AA"
-            } else {
-                false
-            }
-        })
-        .unwrap()
-        .0;
-
-    let end = map
-        .iter()
-        .find(|(_, t)| {
-            if let Tile::Portal(_, p) = t {
-                p == "ZZ"
-            } else {
-                false
-            }
-        })
-        .unwrap()
-        .0;
-
-    let result = bfs(
-        start,
-        |&pos| {
-            let mut neighbors = Vec::new();
-
-            for dir in &[
-                IVec2::new(0, 1),
-                IVec2::new(0, -1),
-                IVec2::new(1, 0),
-                IVec2::new(-1, 0),
-            ] {
-                let new_pos = pos + *dir;
-                if map.contains_key(&new_pos) {
-                    neighbors.push(new_pos);
-                }
-            }
+    fn parse(input: &str) -> ParseResult<Self::Input> {
+        Ok(parse_map_with_portals(input))
+    }
 
-            if let Some(Tile::Portal(_, side1)) = map.get(&pos) {
-                if let Some((p, _)) = map.iter().find(|(p, t)| {
-                    if let Tile::Portal(_, side2) = t {
-                        **p != pos && side1 == side2
-                    } else {
-                        false
+    fn part_one(map: &Self::Input) -> Option<u32> {
+        let start = map
+            .iter()
+            .find(|(_, t)| {
+                if let Tile::Portal(_, p) = t {
+                    p == "AA"
+                } else {
+                    false
+                }
+            })
+            .unwrap()
+            .0;
+
+        let end = map
+            .iter()
+            .find(|(_, t)| {
+                if let Tile::Portal(_, p) = t {
+                    p == "ZZ"
+                } else {
+                    false
+                }
+            })
+            .unwrap()
+            .0;
+
+        let result = bfs(
+            start,
+            |&pos| {
+                let mut neighbors = Vec::new();
+
+                for dir in &[
+                    IVec2::new(0, 1),
+                    IVec2::new(0, -1),
+                    IVec2::new(1, 0),
+                    IVec2::new(-1, 0),
+                ] {
+                    let new_pos = pos + *dir;
+                    if map.contains_key(&new_pos) {
+                        neighbors.push(new_pos);
                     }
-                }) {
-                    neighbors.push(*p);
                 }
-            }
 
-            neighbors
-        },
-        |&pos| pos == *end,
-    );
+                if let Some(Tile::Portal(_, side1)) = map.get(&pos) {
+                    if let Some((p, _)) = map.iter().find(|(p, t)| {
+                        if let Tile::Portal(_, side2) = t {
+                            **p != pos && side1 == side2
+                        } else {
+                            false
+                        }
+                    }) {
+                        neighbors.push(*p);
+                    }
+                }
 
-    result?.len().checked_sub(1).map(|x| x as u32)
-}
+                neighbors
+            },
+            |&pos| pos == *end,
+        );
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let map = parse_map_with_portals(input);
+        result?.len().checked_sub(1).map(|x| x as u32)
+    }
 
-    let start = map
-        .iter()
-        .find(|(_, t)| {
-            if let Tile::Portal(_, p) = t {
-                p == "AA"
-            } else {
-                false
-            }
-        })
-        .unwrap()
-        .0;
-
-    let end = map
-        .iter()
-        .find(|(_, t)| {
-            if let Tile::Portal(_, p) = t {
-                p == "ZZ"
-            } else {
-                false
-            }
-        })
-        .unwrap()
-        .0;
-
-    let min = map.keys().fold(IVec2::new(i32::MAX, i32::MAX), |acc, p| {
-        IVec2::new(acc.x.min(p.x), acc.y.min(p.y))
-    });
-    let max = map.keys().fold(IVec2::new(i32::MIN, i32::MIN), |acc, p| {
-        IVec2::new(acc.x.max(p.x), acc.y.max(p.y))
-    });
-
-    let is_outer_teleport = move |pos: IVec2| -> bool {
-        pos.x == min.x || pos.y == min.y || pos.x == max.x || pos.y == max.y
-    };
-
-    let result = bfs(
-        &(*start, 0),
-        |&(pos, level)| {
-            let mut neighbors = Vec::new();
-
-            for dir in &[
-                IVec2::new(0, 1),
-                IVec2::new(0, -1),
-                IVec2::new(1, 0),
-                IVec2::new(-1, 0),
-            ] {
-                let new_pos = pos + *dir;
-                if map.contains_key(&new_pos) {
-                    neighbors.push((new_pos, level));
+    fn part_two(map: &Self::Input) -> Option<u32> {
+        let start = map
+            .iter()
+            .find(|(_, t)| {
+                if let Tile::Portal(_, p) = t {
+                    p == "AA"
+                } else {
+                    false
                 }
-            }
-
-            if let Some(Tile::Portal(_, side1)) = map.get(&pos) {
-                if let Some((p, _)) = map.iter().find(|(p, t)| {
-                    if let Tile::Portal(_, side2) = t {
-                        **p != pos && side1 == side2
-                    } else {
-                        false
+            })
+            .unwrap()
+            .0;
+
+        let end = map
+            .iter()
+            .find(|(_, t)| {
+                if let Tile::Portal(_, p) = t {
+                    p == "ZZ"
+                } else {
+                    false
+                }
+            })
+            .unwrap()
+            .0;
+
+        let min = map.keys().fold(IVec2::new(i32::MAX, i32::MAX), |acc, p| {
+            IVec2::new(acc.x.min(p.x), acc.y.min(p.y))
+        });
+        let max = map.keys().fold(IVec2::new(i32::MIN, i32::MIN), |acc, p| {
+            IVec2::new(acc.x.max(p.x), acc.y.max(p.y))
+        });
+
+        let is_outer_teleport = move |pos: IVec2| -> bool {
+            pos.x == min.x || pos.y == min.y || pos.x == max.x || pos.y == max.y
+        };
+
+        let result = bfs(
+            &(*start, 0),
+            |&(pos, level)| {
+                let mut neighbors = Vec::new();
+
+                for dir in &[
+                    IVec2::new(0, 1),
+                    IVec2::new(0, -1),
+                    IVec2::new(1, 0),
+                    IVec2::new(-1, 0),
+                ] {
+                    let new_pos = pos + *dir;
+                    if map.contains_key(&new_pos) {
+                        neighbors.push((new_pos, level));
                     }
-                }) {
-                    if is_outer_teleport(pos) {
-                        if level > 0 {
-                            neighbors.push((*p, level - 1));
+                }
+
+                if let Some(Tile::Portal(_, side1)) = map.get(&pos) {
+                    if let Some((p, _)) = map.iter().find(|(p, t)| {
+                        if let Tile::Portal(_, side2) = t {
+                            **p != pos && side1 == side2
+                        } else {
+                            false
+                        }
+                    }) {
+                        if is_outer_teleport(pos) {
+                            if level > 0 {
+                                neighbors.push((*p, level - 1));
+                            }
+                        } else {
+                            neighbors.push((*p, level + 1));
                         }
-                    } else {
-                        neighbors.push((*p, level + 1));
                     }
                 }
-            }
 
-            neighbors
-        },
-        |&(pos, level)| pos == *end && level == 0,
-    );
+                neighbors
+            },
+            |&(pos, level)| pos == *end && level == 0,
+        );
 
-    result?.len().checked_sub(1).map(|x| x as u32)
+        result?.len().checked_sub(1).map(|x| x as u32)
+    }
 }
 
 #[cfg(test)]
@@ -204,15 +214,18 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let input = Day20::parse(&advent_of_code::template::read_file("examples", DAY)).unwrap();
+        let result = Day20::part_one(&input);
         assert_eq!(result, Some(23));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file_part(
+        let input = Day20::parse(&advent_of_code::template::read_file_part(
             "examples", DAY, 2,
-        ));
+        ))
+        .unwrap();
+        let result = Day20::part_two(&input);
         assert_eq!(result, Some(396));
     }
 }
@@ -1,11 +1,32 @@
-use advent_of_code::IntcodeComputer;
+use advent_of_code::template::{ParseResult, Solution};
+use advent_of_code::{load_intcode_program, IntcodeComputer, IntcodeProgram};
 
-advent_of_code::solution!(21);
+advent_of_code::solution!(21, Day21);
 
-pub fn part_one(input: &str) -> Option<i128> {
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Input = IntcodeProgram;
+    type PartOne = i128;
+    type PartTwo = i128;
+
+    fn parse(input: &str) -> ParseResult<Self::Input> {
+        Ok(load_intcode_program(input)?)
+    }
+
+    fn part_one(program: &Self::Input) -> Option<i128> {
+        walk(program)
+    }
+
+    fn part_two(program: &Self::Input) -> Option<i128> {
+        run(program)
+    }
+}
+
+fn walk(program: &IntcodeProgram) -> Option<i128> {
     let mut computer = IntcodeComputer::new();
 
-    computer.load_program_from_str(input);
+    computer.load_program(program);
 
     let input = r"NOT B J
                         NOT C T
@@ -25,10 +46,10 @@ pub fn part_one(input: &str) -> Option<i128> {
     computer.get_next_output()
 }
 
-pub fn part_two(input: &str) -> Option<i128> {
+fn run(program: &IntcodeProgram) -> Option<i128> {
     let mut computer = IntcodeComputer::new();
 
-    computer.load_program_from_str(input);
+    computer.load_program(program);
 
     let input = r"NOT B J
                         NOT C T
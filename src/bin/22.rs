@@ -1,4 +1,29 @@
-advent_of_code::solution!(22);
+use advent_of_code::template::{ParseResult, Solution};
+
+advent_of_code::solution!(22, Day22);
+
+pub struct Day22;
+
+#[derive(Debug, Clone, Copy)]
+pub enum Technique {
+    DealIntoNewStack,
+    Cut(i128),
+    DealWithIncrement(i128),
+}
+
+fn parse_technique(instruction: &str) -> Option<Technique> {
+    let argument = || instruction.split_whitespace().last()?.parse().ok();
+
+    if instruction.starts_with("deal into new stack") {
+        Some(Technique::DealIntoNewStack)
+    } else if instruction.starts_with("cut") {
+        Some(Technique::Cut(argument()?))
+    } else if instruction.starts_with("deal with increment") {
+        Some(Technique::DealWithIncrement(argument()?))
+    } else {
+        None
+    }
+}
 
 fn deal_into_new_stack(deck: &mut [u32]) {
     deck.reverse();
@@ -28,26 +53,12 @@ fn deal_with_increment(deck: &mut Vec<u32>, n: i32) {
     *deck = new_deck;
 }
 
-fn shuffle(deck: &mut Vec<u32>, instructions: &str) {
-    for instruction in instructions.lines() {
-        if instruction.starts_with("deal into new stack") {
-            deal_into_new_stack(deck);
-        } else if instruction.starts_with("cut") {
-            let n = instruction
-                .split_whitespace()
-                .last()
-                .unwrap()
-                .parse()
-                .unwrap();
-            cut(deck, n);
-        } else if instruction.starts_with("deal with increment") {
-            let n = instruction
-                .split_whitespace()
-                .last()
-                .unwrap()
-                .parse()
-                .unwrap();
-            deal_with_increment(deck, n);
+fn shuffle(deck: &mut Vec<u32>, techniques: &[Technique]) {
+    for technique in techniques {
+        match *technique {
+            Technique::DealIntoNewStack => deal_into_new_stack(deck),
+            Technique::Cut(n) => cut(deck, n as i32),
+            Technique::DealWithIncrement(n) => deal_with_increment(deck, n as i32),
         }
     }
 }
@@ -94,13 +105,27 @@ fn modp(b: i128, exp: i128, base: i128) -> i128 {
     x
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let mut deck: Vec<u32> = (0..10007).collect();
-    shuffle(&mut deck, input);
-    deck.iter().position(|&x| x == 2019).map(|x| x as u32)
+impl Solution for Day22 {
+    type Input = Vec<Technique>;
+    type PartOne = u32;
+    type PartTwo = i128;
+
+    fn parse(input: &str) -> ParseResult<Self::Input> {
+        Ok(input.lines().filter_map(parse_technique).collect())
+    }
+
+    fn part_one(techniques: &Self::Input) -> Option<u32> {
+        let mut deck: Vec<u32> = (0..10007).collect();
+        shuffle(&mut deck, techniques);
+        deck.iter().position(|&x| x == 2019).map(|x| x as u32)
+    }
+
+    fn part_two(techniques: &Self::Input) -> Option<i128> {
+        Some(card_at_position(techniques))
+    }
 }
 
-pub fn part_two(input: &str) -> Option<i128> {
+fn card_at_position(techniques: &[Technique]) -> i128 {
     const D: i128 = 119_315_717_514_047;
     const N: i128 = 101_741_582_076_661;
     const TGT: i128 = 2020;
@@ -108,29 +133,21 @@ pub fn part_two(input: &str) -> Option<i128> {
     let mut a = 1;
     let mut b = 0;
 
-    for instruction in input.lines().rev() {
-        if instruction.starts_with("deal into new stack") {
-            b += 1;
-            b *= -1;
-            a *= -1;
-        } else if instruction.starts_with("cut") {
-            let n: i128 = instruction
-                .split_whitespace()
-                .last()
-                .unwrap()
-                .parse()
-                .unwrap();
-            b += if n < 0 { n + D } else { n };
-        } else if instruction.starts_with("deal with increment") {
-            let n: i128 = instruction
-                .split_whitespace()
-                .last()
-                .unwrap()
-                .parse()
-                .unwrap();
-            let inv = modinv(n, D);
-            a = a * inv % D;
-            b = b * inv % D;
+    for technique in techniques.iter().rev() {
+        match *technique {
+            Technique::DealIntoNewStack => {
+                b += 1;
+                b *= -1;
+                a *= -1;
+            }
+            Technique::Cut(n) => {
+                b += if n < 0 { n + D } else { n };
+            }
+            Technique::DealWithIncrement(n) => {
+                let inv = modinv(n, D);
+                a = a * inv % D;
+                b = b * inv % D;
+            }
         }
 
         a %= D;
@@ -149,9 +166,7 @@ pub fn part_two(input: &str) -> Option<i128> {
     let i2 = (modp(a, N, D) + D - 1) % D;
     let i3 = b * i2 % D;
     let i4 = modp(a - 1, D - 2, D);
-    let ans = (i1 + i3 * i4) % D;
-
-    Some(ans)
+    (i1 + i3 * i4) % D
 }
 
 #[cfg(test)]
@@ -163,7 +178,7 @@ mod tests {
         let mut deck: Vec<u32> = (0..10).collect();
         shuffle(
             &mut deck,
-            &advent_of_code::template::read_file("examples", DAY),
+            &Day22::parse(&advent_of_code::template::read_file("examples", DAY)).unwrap(),
         );
         assert_eq!(deck, Vec::from([9, 2, 5, 8, 1, 4, 7, 0, 3, 6]));
     }
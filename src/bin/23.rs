@@ -1,134 +1,150 @@
 use std::collections::HashSet;
+use std::rc::Rc;
 
-use advent_of_code::IntcodeComputer;
+use advent_of_code::intcode::asynchronous::{AsyncIntcodeComputer, Channel, Executor};
+use advent_of_code::template::{ParseResult, Solution};
+use advent_of_code::{load_intcode_program, IntcodeComputer, IntcodeProgram};
 
-advent_of_code::solution!(23);
+advent_of_code::solution!(23, Day23);
 
-struct NetworkInterfaceController {
-    computer: IntcodeComputer,
+pub struct Day23;
+
+const NETWORK_SIZE: usize = 50;
+const NAT_ADDRESS: i128 = 255;
+
+/// The network interface controllers and the routers delivering their packets, all running as
+/// tasks of a single executor. Packets sent to address 255 are queued on `nat`.
+struct Network {
+    executor: Executor<'static>,
+    inputs: Rc<[Channel]>,
+    nat: Channel,
 }
 
-struct NotAlwaysTransmitting {
-    x: i128,
-    y: i128,
+impl Solution for Day23 {
+    type Input = IntcodeProgram;
+    type PartOne = i128;
+    type PartTwo = i128;
+
+    fn parse(input: &str) -> ParseResult<Self::Input> {
+        Ok(load_intcode_program(input)?)
+    }
+
+    fn part_one(program: &Self::Input) -> Option<i128> {
+        first_packet_to_nat(program)
+    }
+
+    fn part_two(program: &Self::Input) -> Option<i128> {
+        first_repeated_wake_up(program)
+    }
 }
 
-pub fn part_one(input: &str) -> Option<i128> {
-    let mut nics = Vec::new();
+impl Network {
+    fn new(program: &IntcodeProgram) -> Self {
+        let mut executor = Executor::new();
+        let inputs: Rc<[Channel]> = (0..NETWORK_SIZE).map(|_| Channel::new()).collect();
+        let nat = Channel::new();
+
+        for (network_address, input) in inputs.iter().enumerate() {
+            let mut computer = IntcodeComputer::new();
+            computer.load_program(program);
+            input.send(network_address as i128);
+
+            let output = Channel::new();
+            let mut nic = AsyncIntcodeComputer::new(computer, input.clone(), output.clone());
+            nic.set_idle_input(-1);
+
+            executor.spawn(async move {
+                nic.run().await;
+            });
+            executor.spawn(route(output, Rc::clone(&inputs), nat.clone()));
+        }
 
-    for network_address in 0..50 {
-        let mut computer = IntcodeComputer::new();
-        computer.load_program_from_str(input);
-        computer.set_default_input(-1);
-        computer.set_input(network_address);
-        computer.run_until_io();
+        Self {
+            executor,
+            inputs,
+            nat,
+        }
+    }
 
-        nics.push(NetworkInterfaceController { computer });
+    /// Runs the network until it is idle, i.e. every controller is polling an empty input.
+    fn run_until_idle(&mut self) {
+        self.executor.run_until_stalled();
     }
 
-    loop {
-        let outputs: Vec<Option<(usize, i128, i128)>> = nics
-            .iter_mut()
-            .map(|nic| {
-                nic.computer.run_until_io();
-
-                if nic.computer.has_output() {
-                    let address = nic.computer.get_next_output()?;
-                    let x = nic.computer.run_until_output()?;
-                    let y = nic.computer.run_until_output()?;
-
-                    Some((address as usize, x, y))
-                } else {
-                    None
-                }
-            })
-            .collect();
-
-        for packet in outputs {
-            if packet.is_none() {
-                continue;
-            }
-
-            let (address, x, y) = packet.unwrap();
-
-            if address == 255 {
-                return Some(y);
-            }
-
-            nics[address].computer.set_input(x);
-            nics[address].computer.set_input(y);
+    /// Removes the packets received by the NAT, returning the last one.
+    fn take_nat_packet(&self) -> Option<(i128, i128)> {
+        let mut packet = None;
+
+        while let (Some(x), Some(y)) = (self.nat.try_recv(), self.nat.try_recv()) {
+            packet = Some((x, y));
         }
+
+        packet
     }
 }
 
-pub fn part_two(input: &str) -> Option<i128> {
-    let mut nics = Vec::new();
-    let mut nat = NotAlwaysTransmitting { x: 0, y: 0 };
-    let mut nat_delivered_y = HashSet::new();
+/// Delivers the packets written to `output`, as address, X and Y, to their destination.
+async fn route(output: Channel, inputs: Rc<[Channel]>, nat: Channel) {
+    loop {
+        let address = output.recv().await;
+        let x = output.recv().await;
+        let y = output.recv().await;
+
+        let destination = if address == NAT_ADDRESS {
+            &nat
+        } else {
+            &inputs[address as usize]
+        };
+
+        destination.send(x);
+        destination.send(y);
+    }
+}
 
-    for network_address in 0..50 {
-        let mut computer = IntcodeComputer::new();
-        computer.load_program_from_str(input);
-        computer.set_default_input(-1);
-        computer.set_input(network_address);
-        computer.run_until_io();
+fn first_packet_to_nat(program: &IntcodeProgram) -> Option<i128> {
+    let mut network = Network::new(program);
 
-        nics.push(NetworkInterfaceController { computer });
-    }
+    // packets sent to the NAT stay queued, the first one is still there once the network is idle.
+    network.run_until_idle();
+
+    network.nat.try_recv()?;
+    network.nat.try_recv()
+}
+
+fn first_repeated_wake_up(program: &IntcodeProgram) -> Option<i128> {
+    let mut network = Network::new(program);
+    let mut nat_packet = None;
+    let mut nat_delivered_y = HashSet::new();
 
     loop {
-        let outputs: Vec<Option<(usize, i128, i128)>> = nics
-            .iter_mut()
-            .map(|nic| {
-                nic.computer.run_until_io();
-
-                if nic.computer.has_output() {
-                    let address = nic.computer.get_next_output()?;
-                    let x = nic.computer.run_until_output()?;
-                    let y = nic.computer.run_until_output()?;
-
-                    Some((address as usize, x, y))
-                } else {
-                    None
-                }
-            })
-            .collect();
-
-        for packet in outputs {
-            if packet.is_none() {
-                continue;
-            }
-
-            let (address, x, y) = packet.unwrap();
-
-            if address == 255 {
-                nat.x = x;
-                nat.y = y;
-
-                if nics.iter().all(|nic| !nic.computer.has_input()) {
-                    nics[0].computer.set_input(nat.x);
-                    nics[0].computer.set_input(nat.y);
-
-                    if nat_delivered_y.contains(&nat.y) {
-                        return Some(y);
-                    } else {
-                        nat_delivered_y.insert(nat.y);
-                    }
-                }
-
-                continue;
-            }
-
-            nics[address].computer.set_input(x);
-            nics[address].computer.set_input(y);
+        network.run_until_idle();
+        nat_packet = network.take_nat_packet().or(nat_packet);
+
+        let (x, y) = nat_packet?;
+        network.inputs[0].send(x);
+        network.inputs[0].send(y);
+
+        if !nat_delivered_y.insert(y) {
+            return Some(y);
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+
     #[test]
     fn test_part_one() {
-        assert_eq!(1, 1);
+        let input = Day23::parse(&advent_of_code::template::read_file("examples", DAY)).unwrap();
+        let result = Day23::part_one(&input);
+        assert_eq!(result, Some(20));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = Day23::parse(&advent_of_code::template::read_file("examples", DAY)).unwrap();
+        let result = Day23::part_two(&input);
+        assert_eq!(result, Some(20));
     }
 }
@@ -1,8 +1,11 @@
 use std::collections::{HashMap, HashSet};
 
+use advent_of_code::template::{ParseResult, Solution};
 use glam::IVec2;
 
-advent_of_code::solution!(24);
+advent_of_code::solution!(24, Day24);
+
+pub struct Day24;
 
 fn parse_map(input: &str) -> HashMap<IVec2, bool> {
     let mut map = HashMap::new();
@@ -81,20 +84,19 @@ struct Grid {
 const DIRS: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, 1), (0, -1)];
 
 impl Grid {
-    fn from_str(input: &str) -> Option<Grid> {
+    fn from_map(map: &HashMap<IVec2, bool>) -> Grid {
         let (mut bugs, mut rows, mut cols) = (HashSet::new(), 0isize, 0isize);
 
-        for (r, row) in input.lines().enumerate() {
-            for (c, cell) in row.chars().enumerate() {
-                cols = cols.max(c as isize + 1);
-                rows = rows.max(r as isize + 1);
-                if cell == '#' {
-                    bugs.insert((r as isize, c as isize, 0));
-                }
+        for (pos, is_bug) in map {
+            let (r, c) = (pos.y as isize, pos.x as isize);
+            cols = cols.max(c + 1);
+            rows = rows.max(r + 1);
+            if *is_bug {
+                bugs.insert((r, c, 0));
             }
         }
 
-        Some(Grid { rows, cols, bugs })
+        Grid { rows, cols, bugs }
     }
 
     fn adj_cells(&self, cell: &Cell) -> Vec<Cell> {
@@ -164,30 +166,40 @@ impl Grid {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let mut map = parse_map(input);
+impl Solution for Day24 {
+    type Input = HashMap<IVec2, bool>;
+    type PartOne = u32;
+    type PartTwo = u32;
 
-    let mut map_history = Vec::new();
-    map_history.push(map.clone());
+    fn parse(input: &str) -> ParseResult<Self::Input> {
+        Ok(parse_map(input))
+    }
 
-    loop {
-        map = tick(&map);
-
-        if map_history.contains(&map) {
-            return Some(biodiversity_rating(&map));
-        }
+    fn part_one(map: &Self::Input) -> Option<u32> {
+        let mut map = map.clone();
 
+        let mut map_history = Vec::new();
         map_history.push(map.clone());
-    }
-}
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let mut grid = Grid::from_str(input).unwrap();
-    for _ in 0..200 {
-        grid.tick();
+        loop {
+            map = tick(&map);
+
+            if map_history.contains(&map) {
+                return Some(biodiversity_rating(&map));
+            }
+
+            map_history.push(map.clone());
+        }
     }
 
-    Some(grid.count() as u32)
+    fn part_two(map: &Self::Input) -> Option<u32> {
+        let mut grid = Grid::from_map(map);
+        for _ in 0..200 {
+            grid.tick();
+        }
+
+        Some(grid.count() as u32)
+    }
 }
 
 #[cfg(test)]
@@ -196,7 +208,8 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let input = Day24::parse(&advent_of_code::template::read_file("examples", DAY)).unwrap();
+        let result = Day24::part_one(&input);
         assert_eq!(result, Some(2129920));
     }
 }
@@ -1,7 +1,10 @@
-use advent_of_code::IntcodeComputer;
+use advent_of_code::template::{ParseResult, Solution};
+use advent_of_code::{load_intcode_program, IntcodeComputer, IntcodeProgram};
 use std::{collections::VecDeque, io};
 
-advent_of_code::solution!(25);
+advent_of_code::solution!(25, Day25);
+
+pub struct Day25;
 
 fn read_input() -> String {
     let mut input = String::new();
@@ -9,9 +12,27 @@ fn read_input() -> String {
     input.trim().to_string()
 }
 
-pub fn part_one(input: &str) -> Option<String> {
+impl Solution for Day25 {
+    type Input = IntcodeProgram;
+    type PartOne = String;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> ParseResult<Self::Input> {
+        Ok(load_intcode_program(input)?)
+    }
+
+    fn part_one(program: &Self::Input) -> Option<String> {
+        Some(find_password(program))
+    }
+
+    fn part_two(_program: &Self::Input) -> Option<u32> {
+        None
+    }
+}
+
+fn find_password(program: &IntcodeProgram) -> String {
     let mut computer = IntcodeComputer::new();
-    computer.load_program_from_str(input);
+    computer.load_program(program);
 
     let mut output_buffer = String::new();
 
@@ -71,11 +92,7 @@ pub fn part_one(input: &str) -> Option<String> {
         }
     }
 
-    Some(result.to_owned())
-}
-
-pub fn part_two(_input: &str) -> Option<u32> {
-    None
+    result.to_owned()
 }
 
 #[cfg(test)]
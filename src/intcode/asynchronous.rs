@@ -0,0 +1,442 @@
+/// Cooperative multitasking for Intcode machines.
+///
+/// An [`AsyncIntcodeComputer`] reads its input from a [`Channel`] and writes its output to
+/// another one. Awaiting input on an empty channel yields to the executor instead of spinning,
+/// so any number of connected machines can run on a single thread. The waiting task is woken by
+/// the next send on that channel.
+///
+/// The bundled executor polls all tasks round-robin. Every send and receive on a channel counts
+/// as activity, a round without any activity means that every task is blocked on input: the
+/// tasks are stalled (or idle, for programs polling with a default input).
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll, Waker};
+
+use crate::IntcodeComputer;
+
+thread_local! {
+    static ACTIVITY: Cell<u64> = const { Cell::new(0) };
+}
+
+fn record_activity() {
+    ACTIVITY.with(|a| a.set(a.get() + 1));
+}
+
+fn activity() -> u64 {
+    ACTIVITY.with(Cell::get)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Debug, Default)]
+struct Queue {
+    values: VecDeque<i128>,
+    /// Tasks waiting for a value, woken by the next send.
+    receivers: Vec<Waker>,
+}
+
+/// An unbounded queue of values connecting machines. Clones share the same queue.
+#[derive(Debug, Clone, Default)]
+pub struct Channel(Rc<RefCell<Queue>>);
+
+impl Channel {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn send(&self, value: i128) {
+        let receivers = {
+            let mut queue = self.0.borrow_mut();
+            queue.values.push_back(value);
+            std::mem::take(&mut queue.receivers)
+        };
+
+        // woken outside the borrow, a waker may poll the receiving task right away.
+        for waker in receivers {
+            waker.wake();
+        }
+
+        record_activity();
+    }
+
+    pub fn try_recv(&self) -> Option<i128> {
+        let value = self.0.borrow_mut().values.pop_front();
+        if value.is_some() {
+            record_activity();
+        }
+        value
+    }
+
+    /// Waits until a value is available.
+    pub fn recv(&self) -> Recv {
+        Recv {
+            channel: self.clone(),
+            idle_value: None,
+            yielded: false,
+        }
+    }
+
+    /// Yields once if the channel is empty, then returns `idle_value` if it is still empty.
+    /// Mirrors programs polling their input with a default value (e.g. `-1` for network cards).
+    pub fn recv_or(&self, idle_value: i128) -> Recv {
+        Recv {
+            channel: self.clone(),
+            idle_value: Some(idle_value),
+            yielded: false,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.borrow().values.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.borrow().values.len()
+    }
+
+    /// Registers `waker` to be woken by the next send.
+    fn register(&self, waker: &Waker) {
+        let receivers = &mut self.0.borrow_mut().receivers;
+        if !receivers.iter().any(|w| w.will_wake(waker)) {
+            receivers.push(waker.clone());
+        }
+    }
+}
+
+/// Future returned by [`Channel::recv`] and [`Channel::recv_or`].
+pub struct Recv {
+    channel: Channel,
+    idle_value: Option<i128>,
+    yielded: bool,
+}
+
+impl Future for Recv {
+    type Output = i128;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if let Some(value) = self.channel.try_recv() {
+            return Poll::Ready(value);
+        }
+
+        match self.idle_value {
+            Some(value) if self.yielded => Poll::Ready(value),
+            Some(_) => {
+                // only yields to other tasks, so it has to be polled again without a send.
+                self.yielded = true;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+            None => {
+                self.channel.register(cx.waker());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An [`IntcodeComputer`] connected to an input and an output channel.
+pub struct AsyncIntcodeComputer {
+    computer: IntcodeComputer,
+    input: Channel,
+    output: Channel,
+    idle_input: Option<i128>,
+}
+
+impl AsyncIntcodeComputer {
+    pub fn new(computer: IntcodeComputer, input: Channel, output: Channel) -> Self {
+        Self {
+            computer,
+            input,
+            output,
+            idle_input: None,
+        }
+    }
+
+    /// Feeds `value` to the program when its input channel is empty instead of blocking.
+    /// The machine still yields to other tasks before doing so.
+    pub fn set_idle_input(&mut self, value: i128) {
+        self.idle_input = Some(value);
+    }
+
+    /// Runs the program until it halts and returns the computer in its final state.
+    pub async fn run(mut self) -> IntcodeComputer {
+        while self.computer.is_running() {
+            if self.computer.awaits_input() {
+                let value = match self.idle_input {
+                    Some(idle) => self.input.recv_or(idle).await,
+                    None => self.input.recv().await,
+                };
+
+                self.computer.set_input(value);
+            }
+
+            self.computer.step();
+
+            for value in self.computer.take_output() {
+                self.output.send(value);
+            }
+        }
+
+        self.computer
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+type Task<'a> = Pin<Box<dyn Future<Output = ()> + 'a>>;
+
+/// The result of running an [`Executor`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    /// All tasks ran to completion.
+    Completed,
+    /// All remaining tasks are waiting for input that nobody sends.
+    Stalled,
+}
+
+/// A minimal single-threaded executor polling its tasks round-robin.
+#[derive(Default)]
+pub struct Executor<'a> {
+    tasks: Vec<Option<Task<'a>>>,
+}
+
+impl<'a> Executor<'a> {
+    pub fn new() -> Self {
+        Self { tasks: vec![] }
+    }
+
+    pub fn spawn(&mut self, future: impl Future<Output = ()> + 'a) {
+        self.tasks.push(Some(Box::pin(future)));
+    }
+
+    /// Number of tasks that have not completed yet.
+    pub fn pending(&self) -> usize {
+        self.tasks.iter().flatten().count()
+    }
+
+    /// Polls all tasks until they complete or a full round passes without channel activity.
+    /// Can be called again after sending new values to resume stalled tasks.
+    pub fn run_until_stalled(&mut self) -> Status {
+        let mut cx = Context::from_waker(Waker::noop());
+
+        loop {
+            let before = activity();
+
+            for slot in self.tasks.iter_mut() {
+                if let Some(task) = slot {
+                    if task.as_mut().poll(&mut cx).is_ready() {
+                        *slot = None;
+                    }
+                }
+            }
+
+            self.tasks.retain(Option::is_some);
+
+            if self.tasks.is_empty() {
+                return Status::Completed;
+            }
+
+            if activity() == before {
+                return Status::Stalled;
+            }
+        }
+    }
+}
+
+/// Drives a future to completion on the current thread.
+/// Returns `None` if it stalls, i.e. it is pending without any channel activity.
+pub fn block_on<F: Future>(future: F) -> Option<F::Output> {
+    let mut cx = Context::from_waker(Waker::noop());
+    let mut future = std::pin::pin!(future);
+
+    loop {
+        let before = activity();
+
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return Some(output);
+        }
+
+        if activity() == before {
+            return None;
+        }
+    }
+}
+
+/// Future returned by [`join_all`].
+pub struct JoinAll<F: Future> {
+    futures: Vec<Option<Pin<Box<F>>>>,
+    outputs: Vec<Option<F::Output>>,
+}
+
+// the futures are boxed and the outputs are never pinned.
+impl<F: Future> Unpin for JoinAll<F> {}
+
+/// Polls all futures concurrently, resolving to their outputs in order.
+pub fn join_all<F: Future>(futures: impl IntoIterator<Item = F>) -> JoinAll<F> {
+    let futures: Vec<_> = futures.into_iter().map(|f| Some(Box::pin(f))).collect();
+    let outputs = futures.iter().map(|_| None).collect();
+
+    JoinAll { futures, outputs }
+}
+
+impl<F: Future> Future for JoinAll<F> {
+    type Output = Vec<F::Output>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        for (slot, output) in this.futures.iter_mut().zip(this.outputs.iter_mut()) {
+            if let Some(future) = slot {
+                if let Poll::Ready(value) = future.as_mut().poll(cx) {
+                    *output = Some(value);
+                    *slot = None;
+                }
+            }
+        }
+
+        if this.futures.iter().all(Option::is_none) {
+            Poll::Ready(this.outputs.iter_mut().filter_map(Option::take).collect())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::cell::Cell;
+    use std::future::Future;
+    use std::pin::pin;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::task::{Context, Poll, Wake, Waker};
+
+    use super::{block_on, join_all, AsyncIntcodeComputer, Channel, Executor, Status};
+    use crate::IntcodeComputer;
+
+    struct Flag(AtomicBool);
+
+    impl Wake for Flag {
+        fn wake(self: Arc<Self>) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    /// Polls `future` again only after it was woken, like executors that park until notified.
+    /// Returns `None` if it is pending without having been woken.
+    fn run_when_woken<F: Future>(future: F) -> Option<F::Output> {
+        let flag = Arc::new(Flag(AtomicBool::new(true)));
+        let waker = Waker::from(Arc::clone(&flag));
+        let mut cx = Context::from_waker(&waker);
+        let mut future = pin!(future);
+
+        while flag.0.swap(false, Ordering::SeqCst) {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return Some(output);
+            }
+        }
+
+        None
+    }
+
+    fn machine(program: &str, input: &Channel, output: &Channel) -> AsyncIntcodeComputer {
+        let mut computer = IntcodeComputer::new();
+        computer.load_program_from_str(program).unwrap();
+        AsyncIntcodeComputer::new(computer, input.clone(), output.clone())
+    }
+
+    #[test]
+    fn runs_feedback_loops() {
+        // day 7 part two example.
+        let program =
+            "3,26,1001,26,-4,26,3,27,1002,27,2,27,1,27,26,27,4,27,1001,28,-1,28,1005,28,6,99,0,0,5";
+        let channels: Vec<Channel> = (0..5).map(|_| Channel::new()).collect();
+
+        for (channel, phase) in channels.iter().zip([9, 8, 7, 6, 5]) {
+            channel.send(phase);
+        }
+        channels[0].send(0);
+
+        let amplifiers =
+            (0..5).map(|i| machine(program, &channels[i], &channels[(i + 1) % 5]).run());
+
+        let computers = block_on(join_all(amplifiers)).unwrap();
+        assert!(computers.iter().all(|c| !c.is_running()));
+        assert_eq!(channels[0].try_recv(), Some(139629729));
+    }
+
+    #[test]
+    fn wakes_receivers_on_send() {
+        // doubles its input.
+        let program = "3,9,1002,9,2,10,4,10,99,0,0";
+        let (a, b, c) = (Channel::new(), Channel::new(), Channel::new());
+
+        // polled first, the second machine has to wait for the output of the first one.
+        let machines = [
+            machine(program, &b, &c).run(),
+            machine(program, &a, &b).run(),
+        ];
+        a.send(5);
+
+        let computers = run_when_woken(join_all(machines)).unwrap();
+        assert!(computers.iter().all(|c| !c.is_running()));
+        assert_eq!(c.try_recv(), Some(20));
+    }
+
+    #[test]
+    fn detects_stalled_machines() {
+        // echoes input values forever.
+        let program = "3,7,4,7,1105,1,0,0";
+        let (a, b) = (Channel::new(), Channel::new());
+        let received = Cell::new(0);
+
+        let mut executor = Executor::new();
+        executor.spawn(async {
+            machine(program, &a, &b).run().await;
+        });
+
+        a.send(42);
+        assert_eq!(executor.run_until_stalled(), Status::Stalled);
+        assert_eq!(b.try_recv(), Some(42));
+
+        executor.spawn(async {
+            b.recv().await;
+            received.set(received.get() + 1);
+        });
+        a.send(7);
+
+        assert_eq!(executor.run_until_stalled(), Status::Stalled);
+        assert_eq!(executor.pending(), 1);
+        assert_eq!(received.get(), 1);
+    }
+
+    #[test]
+    fn idles_with_default_input() {
+        // polls its input until it reads something other than -1, then outputs it.
+        let program = "3,12,1008,12,-1,13,1005,13,0,4,12,99,0,0";
+        let (input, output) = (Channel::new(), Channel::new());
+
+        let mut nic = machine(program, &input, &output);
+        nic.set_idle_input(-1);
+
+        let mut executor = Executor::new();
+        executor.spawn(async {
+            nic.run().await;
+        });
+
+        assert_eq!(executor.run_until_stalled(), Status::Stalled);
+        assert_eq!(executor.run_until_stalled(), Status::Stalled);
+        assert!(output.is_empty());
+
+        input.send(5);
+        assert_eq!(executor.run_until_stalled(), Status::Completed);
+        assert_eq!(output.try_recv(), Some(5));
+    }
+}
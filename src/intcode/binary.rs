@@ -0,0 +1,308 @@
+/// Compact binary image format for Intcode programs.
+///
+/// Layout (all multi-byte integers are unsigned LEB128 varints):
+///
+/// | field     | size                                                        |
+/// | --------- | ----------------------------------------------------------- |
+/// | magic     | 4 bytes, `ICBN`                                             |
+/// | version   | 1 byte                                                      |
+/// | word size | 1 byte, width in bytes of the widest value (1, 2, 4, 8, 16) |
+/// | length    | varint, number of words                                     |
+/// | words     | zigzag-encoded varints                                      |
+use std::error::Error;
+use std::fmt::Display;
+use std::io::{self, Read, Write};
+
+use crate::IntcodeProgram;
+
+pub const MAGIC: &[u8; 4] = b"ICBN";
+pub const VERSION: u8 = 1;
+
+const WORD_SIZES: [u8; 5] = [1, 2, 4, 8, 16];
+
+/// An error which can be returned when reading a binary program image.
+#[derive(Debug)]
+pub enum BinaryFormatError {
+    IO(io::Error),
+    BadMagic,
+    UnsupportedVersion(u8),
+    InvalidWordSize(u8),
+    Truncated,
+    /// A varint does not fit into the declared word size.
+    Overflow {
+        index: usize,
+    },
+    /// A varint does not fit into 128 bits.
+    VarintOverflow,
+    TrailingData,
+}
+
+impl Error for BinaryFormatError {}
+
+impl Display for BinaryFormatError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BinaryFormatError::IO(e) => write!(f, "{e}"),
+            BinaryFormatError::BadMagic => f.write_str("not an Intcode binary image."),
+            BinaryFormatError::UnsupportedVersion(v) => {
+                write!(f, "unsupported binary image version {v}.")
+            }
+            BinaryFormatError::InvalidWordSize(s) => write!(f, "invalid word size {s}."),
+            BinaryFormatError::Truncated => f.write_str("binary image is truncated."),
+            BinaryFormatError::Overflow { index } => {
+                write!(f, "word #{index} does not fit the declared word size.")
+            }
+            BinaryFormatError::VarintOverflow => f.write_str("varint does not fit into 128 bits."),
+            BinaryFormatError::TrailingData => {
+                f.write_str("binary image contains data after the last word.")
+            }
+        }
+    }
+}
+
+impl From<io::Error> for BinaryFormatError {
+    fn from(e: io::Error) -> Self {
+        BinaryFormatError::IO(e)
+    }
+}
+
+/// Returns `true` if `bytes` start with the binary image magic.
+pub fn is_binary_program(bytes: &[u8]) -> bool {
+    bytes.starts_with(MAGIC)
+}
+
+/// Encodes a program into a binary image.
+pub fn encode_program(program: &IntcodeProgram) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(MAGIC.len() + 2 + program.len() * 2);
+
+    bytes.extend_from_slice(MAGIC);
+    bytes.push(VERSION);
+    bytes.push(word_size(program));
+    write_varint(&mut bytes, program.len() as u128);
+
+    for word in program {
+        write_varint(&mut bytes, zigzag_encode(*word));
+    }
+
+    bytes
+}
+
+/// Decodes a binary image into a program.
+pub fn decode_program(bytes: &[u8]) -> Result<IntcodeProgram, BinaryFormatError> {
+    let body = bytes
+        .strip_prefix(MAGIC)
+        .ok_or(BinaryFormatError::BadMagic)?;
+
+    let [version, word_size, rest @ ..] = body else {
+        return Err(BinaryFormatError::Truncated);
+    };
+
+    if *version != VERSION {
+        return Err(BinaryFormatError::UnsupportedVersion(*version));
+    }
+
+    if !WORD_SIZES.contains(word_size) {
+        return Err(BinaryFormatError::InvalidWordSize(*word_size));
+    }
+
+    let mut cursor = rest;
+    let len =
+        usize::try_from(read_varint(&mut cursor)?).map_err(|_| BinaryFormatError::Truncated)?;
+
+    // every word takes at least one byte, this guards against bogus length headers.
+    if len > cursor.len() {
+        return Err(BinaryFormatError::Truncated);
+    }
+
+    let mut program = IntcodeProgram::with_capacity(len);
+
+    for index in 0..len {
+        let word = zigzag_decode(read_varint(&mut cursor)?);
+
+        if !fits(word, *word_size) {
+            return Err(BinaryFormatError::Overflow { index });
+        }
+
+        program.push(word);
+    }
+
+    if !cursor.is_empty() {
+        return Err(BinaryFormatError::TrailingData);
+    }
+
+    Ok(program)
+}
+
+/// Writes the binary image of a program to `writer`.
+pub fn write_binary_program(program: &IntcodeProgram, mut writer: impl Write) -> io::Result<()> {
+    writer.write_all(&encode_program(program))
+}
+
+/// Reads a binary image from `reader`.
+pub fn read_binary_program(mut reader: impl Read) -> Result<IntcodeProgram, BinaryFormatError> {
+    let mut bytes = vec![];
+    reader.read_to_end(&mut bytes)?;
+    decode_program(&bytes)
+}
+
+fn word_size(program: &IntcodeProgram) -> u8 {
+    WORD_SIZES
+        .into_iter()
+        .find(|size| program.iter().all(|word| fits(*word, *size)))
+        .unwrap_or(16)
+}
+
+fn fits(word: i128, word_size: u8) -> bool {
+    let bits = u32::from(word_size) * 8;
+    if bits >= 128 {
+        return true;
+    }
+
+    let max = (1_i128 << (bits - 1)) - 1;
+    let min = -(1_i128 << (bits - 1));
+    (min..=max).contains(&word)
+}
+
+fn zigzag_encode(word: i128) -> u128 {
+    ((word << 1) ^ (word >> 127)) as u128
+}
+
+fn zigzag_decode(value: u128) -> i128 {
+    ((value >> 1) as i128) ^ -((value & 1) as i128)
+}
+
+fn write_varint(bytes: &mut Vec<u8>, mut value: u128) {
+    loop {
+        #[allow(clippy::cast_possible_truncation)]
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+
+        if value == 0 {
+            bytes.push(byte);
+            return;
+        }
+
+        bytes.push(byte | 0x80);
+    }
+}
+
+fn read_varint(cursor: &mut &[u8]) -> Result<u128, BinaryFormatError> {
+    let mut value = 0_u128;
+    let mut shift = 0;
+
+    loop {
+        let (byte, rest) = cursor.split_first().ok_or(BinaryFormatError::Truncated)?;
+        *cursor = rest;
+
+        let bits = u128::from(byte & 0x7f);
+
+        // the last byte of a 128 bit value holds only its 2 highest bits.
+        if shift >= 128 || bits.leading_zeros() < shift {
+            return Err(BinaryFormatError::VarintOverflow);
+        }
+
+        value |= bits << shift;
+
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+
+        shift += 7;
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{decode_program, encode_program, BinaryFormatError, MAGIC};
+
+    #[test]
+    fn round_trips_programs() {
+        let programs = [
+            vec![1, 9, 10, 3, 2, 3, 11, 0, 99, 30, 40, 50],
+            vec![104, 1125899906842624, 99],
+            vec![-1, 0, 1, i128::MIN, i128::MAX],
+            vec![],
+        ];
+
+        for program in programs {
+            assert_eq!(decode_program(&encode_program(&program)).unwrap(), program);
+        }
+    }
+
+    #[test]
+    fn picks_smallest_word_size() {
+        assert_eq!(encode_program(&vec![1, 2, 99])[5], 1);
+        assert_eq!(encode_program(&vec![1, 2, 1000])[5], 2);
+        assert_eq!(encode_program(&vec![1125899906842624])[5], 8);
+        assert_eq!(encode_program(&vec![i128::MAX])[5], 16);
+    }
+
+    #[test]
+    fn rejects_invalid_images() {
+        assert!(matches!(
+            decode_program(b"1,2,3"),
+            Err(BinaryFormatError::BadMagic)
+        ));
+
+        let mut bytes = encode_program(&vec![1, 2, 99]);
+        bytes.pop();
+        assert!(matches!(
+            decode_program(&bytes),
+            Err(BinaryFormatError::Truncated)
+        ));
+
+        let mut bytes = encode_program(&vec![1, 2, 99]);
+        bytes.push(0);
+        assert!(matches!(
+            decode_program(&bytes),
+            Err(BinaryFormatError::TrailingData)
+        ));
+
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend_from_slice(&[2, 1, 0]);
+        assert!(matches!(
+            decode_program(&bytes),
+            Err(BinaryFormatError::UnsupportedVersion(2))
+        ));
+    }
+
+    #[test]
+    fn rejects_overlong_varints() {
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend_from_slice(&[1, 16, 1]);
+        bytes.extend_from_slice(&[0xff; 18]);
+        bytes.push(0x7f);
+        assert!(matches!(
+            decode_program(&bytes),
+            Err(BinaryFormatError::VarintOverflow)
+        ));
+
+        // the highest value that fits still decodes.
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend_from_slice(&[1, 16, 1]);
+        bytes.extend_from_slice(&[0xff; 18]);
+        bytes.push(0x03);
+        assert_eq!(decode_program(&bytes).unwrap(), vec![i128::MIN]);
+
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend_from_slice(&[1, 1, 1]);
+        bytes.extend_from_slice(&[0x80; 19]);
+        bytes.push(0x00);
+        assert!(matches!(
+            decode_program(&bytes),
+            Err(BinaryFormatError::VarintOverflow)
+        ));
+    }
+
+    #[test]
+    fn rejects_words_wider_than_declared() {
+        let mut bytes = encode_program(&vec![1000]);
+        bytes[5] = 1;
+        assert!(matches!(
+            decode_program(&bytes),
+            Err(BinaryFormatError::Overflow { index: 0 })
+        ));
+    }
+}
@@ -0,0 +1,370 @@
+/// Threaded-code execution engine for [`IntcodeComputer`].
+///
+/// Straight-line runs of instructions are decoded once into blocks of pre-resolved instructions
+/// (handler function + operands with their parameter modes already applied). A block ends after
+/// the first instruction that can change control flow or perform I/O, so the run loops can check
+/// their stop conditions between blocks just like the interpreter does between ticks.
+/// Input instructions additionally always start a new block.
+///
+/// Writes into memory that was decoded into a block invalidate the whole cache. Execution then
+/// continues in freshly decoded blocks, which keeps self-modifying programs correct.
+/// Anything the decoder does not understand (invalid opcodes or modes, code running past the end
+/// of memory) is left to the interpreter, so both engines fail the same way.
+use std::env;
+use std::sync::{Arc, OnceLock};
+
+use crate::IntcodeComputer;
+
+/// Selects how an [`IntcodeComputer`] executes programs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Engine {
+    Interpreter,
+    Compiled,
+}
+
+impl Engine {
+    /// The engine used by new computers. Can be set with the `INTCODE_ENGINE` environment
+    /// variable (`interpreter` or `compiled`), defaults to the interpreter.
+    pub fn from_env() -> Self {
+        static ENGINE: OnceLock<Engine> = OnceLock::new();
+
+        *ENGINE.get_or_init(|| match env::var("INTCODE_ENGINE").as_deref() {
+            Ok("compiled") => Engine::Compiled,
+            _ => Engine::Interpreter,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Operand {
+    Immediate(i128),
+    Position(usize),
+    Relative(i128),
+}
+
+type Handler = fn(&mut IntcodeComputer, &Instruction);
+
+#[derive(Debug, Clone, Copy)]
+struct Instruction {
+    handler: Handler,
+    operands: [Operand; 3],
+}
+
+type Block = Arc<[Instruction]>;
+
+/// Decoded blocks keyed by their start address.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CodeCache {
+    blocks: Vec<Option<Block>>,
+    /// Addresses that were decoded into at least one block.
+    covered: Vec<bool>,
+    /// Set when a write hits decoded code. Checked after every instruction of a block.
+    invalidated: bool,
+}
+
+impl CodeCache {
+    /// Called for every memory write, drops all blocks if `address` was decoded.
+    pub(crate) fn notify_write(&mut self, address: usize) {
+        if self.covered.get(address).copied().unwrap_or(false) {
+            self.blocks.clear();
+            self.covered.clear();
+            self.invalidated = true;
+        }
+    }
+
+    fn block(&self, address: usize) -> Option<Block> {
+        self.blocks.get(address)?.clone()
+    }
+
+    fn insert(&mut self, address: usize, len: usize, block: Block) {
+        if self.blocks.len() <= address {
+            self.blocks.resize(address + 1, None);
+        }
+        if self.covered.len() < address + len {
+            self.covered.resize(address + len, false);
+        }
+
+        self.covered[address..address + len].fill(true);
+        self.blocks[address] = Some(block);
+    }
+}
+
+impl IntcodeComputer {
+    /// Executes the block at the instruction pointer, decoding it first if necessary.
+    /// Falls back to a single interpreter tick for code the decoder cannot handle.
+    pub(crate) fn step_compiled(&mut self) {
+        let address = self.instruction_pointer;
+
+        let block = match self.code.block(address) {
+            Some(block) => block,
+            None => match compile_block(&self.memory, address) {
+                Some((block, len)) => {
+                    self.code.insert(address, len, Arc::clone(&block));
+                    block
+                }
+                None => {
+                    self.tick();
+                    return;
+                }
+            },
+        };
+
+        self.code.invalidated = false;
+
+        for instruction in block.iter() {
+            self.ticks += 1;
+            self.input_requested = false;
+
+            (instruction.handler)(self, instruction);
+
+            if self.code.invalidated {
+                self.code.invalidated = false;
+                break;
+            }
+        }
+    }
+
+    fn read_operand(&mut self, operand: Operand) -> i128 {
+        match operand {
+            Operand::Immediate(value) => value,
+            Operand::Position(address) => self.get(address).unwrap(),
+            Operand::Relative(offset) => self.get((self.relative_base + offset) as usize).unwrap(),
+        }
+    }
+
+    fn destination(&self, operand: Operand) -> usize {
+        match operand {
+            Operand::Position(address) => address,
+            Operand::Relative(offset) => (self.relative_base + offset) as usize,
+            Operand::Immediate(_) => unreachable!("immediate destinations are not compiled"),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn compile_block(memory: &[i128], start: usize) -> Option<(Block, usize)> {
+    let mut instructions = vec![];
+    let mut address = start;
+
+    while let Some((instruction, len, ends_block)) = decode(memory, address) {
+        // input instructions always start a block, so callers can check for pending input
+        // before executing one.
+        if memory[address] % 100 == 3 && !instructions.is_empty() {
+            break;
+        }
+
+        instructions.push(instruction);
+        address += len;
+
+        if ends_block {
+            break;
+        }
+    }
+
+    if instructions.is_empty() {
+        None
+    } else {
+        Some((instructions.into(), address - start))
+    }
+}
+
+/// Decodes a single instruction. Returns its length and whether it terminates a block.
+fn decode(memory: &[i128], address: usize) -> Option<(Instruction, usize, bool)> {
+    let word = *memory.get(address)?;
+
+    // (handler, number of parameters, index of the written parameter, ends block)
+    let (handler, parameters, write, ends_block): (Handler, usize, Option<usize>, bool) =
+        match word % 100 {
+            1 => (op_add, 3, Some(2), false),
+            2 => (op_mul, 3, Some(2), false),
+            3 => (op_in, 1, Some(0), true),
+            4 => (op_out, 1, None, true),
+            5 => (op_jump_if_true, 2, None, true),
+            6 => (op_jump_if_false, 2, None, true),
+            7 => (op_lt, 3, Some(2), false),
+            8 => (op_eq, 3, Some(2), false),
+            9 => (op_adj_base, 1, None, false),
+            99 => (op_exit, 0, None, true),
+            _ => return None,
+        };
+
+    let mut operands = [Operand::Immediate(0); 3];
+
+    for (i, operand) in operands.iter_mut().enumerate().take(parameters) {
+        let value = *memory.get(address + i + 1)?;
+        let mode = word as usize / 10_usize.pow(i as u32 + 2) % 10;
+
+        *operand = match mode {
+            0 => Operand::Position(value as usize),
+            1 if write != Some(i) => Operand::Immediate(value),
+            2 => Operand::Relative(value),
+            _ => return None,
+        };
+    }
+
+    Some((
+        Instruction { handler, operands },
+        parameters + 1,
+        ends_block,
+    ))
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn op_add(c: &mut IntcodeComputer, i: &Instruction) {
+    let a = c.read_operand(i.operands[0]);
+    let b = c.read_operand(i.operands[1]);
+    let output = c.destination(i.operands[2]);
+
+    c.set(output, a + b);
+    c.instruction_pointer += 4;
+}
+
+fn op_mul(c: &mut IntcodeComputer, i: &Instruction) {
+    let a = c.read_operand(i.operands[0]);
+    let b = c.read_operand(i.operands[1]);
+    let output = c.destination(i.operands[2]);
+
+    c.set(output, a * b);
+    c.instruction_pointer += 4;
+}
+
+fn op_in(c: &mut IntcodeComputer, i: &Instruction) {
+    let output = c.destination(i.operands[0]);
+
+    c.input_requested = true;
+
+    if let Some(input) = c.get_input() {
+        c.set(output, input);
+    }
+
+    c.instruction_pointer += 2;
+}
+
+fn op_out(c: &mut IntcodeComputer, i: &Instruction) {
+    let output = c.read_operand(i.operands[0]);
+
+    c.set_output(output);
+    c.instruction_pointer += 2;
+}
+
+fn op_jump_if_true(c: &mut IntcodeComputer, i: &Instruction) {
+    let a = c.read_operand(i.operands[0]);
+    let b = c.read_operand(i.operands[1]);
+
+    if a != 0 {
+        c.instruction_pointer = b as usize;
+    } else {
+        c.instruction_pointer += 3;
+    }
+}
+
+fn op_jump_if_false(c: &mut IntcodeComputer, i: &Instruction) {
+    let a = c.read_operand(i.operands[0]);
+    let b = c.read_operand(i.operands[1]);
+
+    if a == 0 {
+        c.instruction_pointer = b as usize;
+    } else {
+        c.instruction_pointer += 3;
+    }
+}
+
+fn op_lt(c: &mut IntcodeComputer, i: &Instruction) {
+    let a = c.read_operand(i.operands[0]);
+    let b = c.read_operand(i.operands[1]);
+    let output = c.destination(i.operands[2]);
+
+    c.set(output, i128::from(a < b));
+    c.instruction_pointer += 4;
+}
+
+fn op_eq(c: &mut IntcodeComputer, i: &Instruction) {
+    let a = c.read_operand(i.operands[0]);
+    let b = c.read_operand(i.operands[1]);
+    let output = c.destination(i.operands[2]);
+
+    c.set(output, i128::from(a == b));
+    c.instruction_pointer += 4;
+}
+
+fn op_adj_base(c: &mut IntcodeComputer, i: &Instruction) {
+    let a = c.read_operand(i.operands[0]);
+
+    c.relative_base += a;
+    c.instruction_pointer += 2;
+}
+
+fn op_exit(c: &mut IntcodeComputer, _i: &Instruction) {
+    c.is_running = false;
+    c.instruction_pointer += 1;
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Engine;
+    use crate::IntcodeComputer;
+
+    fn run_with(engine: Engine, program: &str, input: &[i128]) -> IntcodeComputer {
+        let mut computer = IntcodeComputer::new();
+        computer.set_engine(engine);
+        computer.load_program_from_str(program).unwrap();
+
+        for value in input {
+            computer.set_input(*value);
+        }
+
+        computer.run();
+        computer
+    }
+
+    fn assert_same_result(program: &str, input: &[i128]) {
+        let interpreted = run_with(Engine::Interpreter, program, input);
+        let compiled = run_with(Engine::Compiled, program, input);
+
+        assert_eq!(interpreted.get_output(), compiled.get_output());
+        assert_eq!(interpreted.memory_snapshot(), compiled.memory_snapshot());
+        assert_eq!(interpreted.ticks(), compiled.ticks());
+    }
+
+    #[test]
+    fn matches_interpreter() {
+        assert_same_result("1,9,10,3,2,3,11,0,99,30,40,50", &[]);
+        assert_same_result("3,9,8,9,10,9,4,9,99,-1,8", &[8]);
+        assert_same_result("3,3,1105,-1,9,1101,0,0,12,4,12,99,1", &[0]);
+        assert_same_result("1102,34915192,34915192,7,4,7,99,0", &[]);
+        assert_same_result(
+            "109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99",
+            &[],
+        );
+    }
+
+    #[test]
+    fn handles_self_modifying_code() {
+        // the first instruction rewrites the opcode of the second from `add` to `mul`.
+        let program = "1101,1,1,4,1,0,0,12,4,12,99,0,0";
+        assert_same_result(program, &[]);
+
+        let computer = run_with(Engine::Compiled, program, &[]);
+        assert_eq!(computer.get_output(), &[1101 * 1101]);
+    }
+
+    #[test]
+    fn stops_at_io() {
+        let mut computer = IntcodeComputer::new();
+        computer.set_engine(Engine::Compiled);
+        computer
+            .load_program_from_str("3,0,4,0,3,0,4,0,99")
+            .unwrap();
+
+        computer.set_input(7);
+        assert_eq!(computer.run_until_output(), Some(7));
+
+        computer.run_until_io();
+        assert!(!computer.has_output());
+        assert!(computer.is_running());
+    }
+}
@@ -0,0 +1,5 @@
+pub mod asynchronous;
+pub mod binary;
+pub mod compiled;
+pub mod parser;
+pub mod symbolic;
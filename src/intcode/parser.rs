@@ -0,0 +1,243 @@
+/// Parser for the comma-separated Intcode program text format.
+///
+/// Two flavours are offered:
+///  1. [`parse_intcode_program`] is lenient: whitespace and newlines are allowed around values and
+///     whole lines starting with `#` are treated as comments.
+///  2. [`parse_intcode_program_strict`] only accepts the canonical format (values separated by
+///     single commas, optionally followed by trailing newlines). Use this to validate inputs in CI.
+use std::error::Error;
+use std::fmt::Display;
+
+use crate::IntcodeProgram;
+
+const COMMENT_PREFIX: char = '#';
+
+/// The reason an Intcode program could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// The input did not contain a single value.
+    EmptyProgram,
+    /// Two separators follow each other, or the program ends with a separator.
+    EmptyToken,
+    /// The token is not a valid integer.
+    InvalidNumber(String),
+    /// Whitespace was encountered in strict mode.
+    UnexpectedWhitespace,
+    /// A comment line was encountered in strict mode.
+    UnexpectedComment,
+}
+
+/// An error which can be returned when parsing an [`IntcodeProgram`].
+///
+/// `token_index` is the zero-based position of the offending value in the program,
+/// `byte_offset` points into the original input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    pub token_index: usize,
+    pub byte_offset: usize,
+}
+
+impl Error for ParseError {}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let ParseError {
+            token_index,
+            byte_offset,
+            ..
+        } = self;
+
+        match &self.kind {
+            ParseErrorKind::EmptyProgram => f.write_str("program does not contain any values"),
+            ParseErrorKind::EmptyToken => {
+                write!(f, "empty value #{token_index} at byte {byte_offset}")
+            }
+            ParseErrorKind::InvalidNumber(token) => write!(
+                f,
+                "invalid value #{token_index} `{token}` at byte {byte_offset}"
+            ),
+            ParseErrorKind::UnexpectedWhitespace => {
+                write!(f, "unexpected whitespace at byte {byte_offset}")
+            }
+            ParseErrorKind::UnexpectedComment => {
+                write!(f, "unexpected comment at byte {byte_offset}")
+            }
+        }
+    }
+}
+
+/// Parses a program, tolerating whitespace and `#` comment lines.
+pub fn parse_intcode_program(input: &str) -> Result<IntcodeProgram, ParseError> {
+    parse(input, false)
+}
+
+/// Parses a program in the canonical format. Only trailing newlines are tolerated.
+pub fn parse_intcode_program_strict(input: &str) -> Result<IntcodeProgram, ParseError> {
+    parse(input, true)
+}
+
+/// A single comma-separated value, stripped of surrounding whitespace and comment lines.
+struct Token {
+    text: String,
+    /// Offset of the first non-whitespace character, or of the separator for empty tokens.
+    offset: Option<usize>,
+}
+
+impl Token {
+    fn new() -> Self {
+        Self {
+            text: String::new(),
+            offset: None,
+        }
+    }
+}
+
+fn parse(input: &str, strict: bool) -> Result<IntcodeProgram, ParseError> {
+    let body = input.trim_end_matches(['\n', '\r']);
+
+    let mut program = IntcodeProgram::new();
+    let mut token = Token::new();
+    let mut line_start = 0;
+
+    for line in body.split_inclusive('\n') {
+        if line.trim_start().starts_with(COMMENT_PREFIX) {
+            if strict {
+                return Err(ParseError {
+                    kind: ParseErrorKind::UnexpectedComment,
+                    token_index: program.len(),
+                    byte_offset: line_start,
+                });
+            }
+
+            line_start += line.len();
+            continue;
+        }
+
+        for (i, c) in line.char_indices() {
+            let offset = line_start + i;
+
+            if c == ',' {
+                program.push(finish_token(&token, program.len(), offset)?);
+                token = Token::new();
+            } else if c.is_whitespace() {
+                if strict {
+                    return Err(ParseError {
+                        kind: ParseErrorKind::UnexpectedWhitespace,
+                        token_index: program.len(),
+                        byte_offset: offset,
+                    });
+                }
+
+                if token.offset.is_some() {
+                    token.text.push(c);
+                }
+            } else {
+                token.offset.get_or_insert(offset);
+                token.text.push(c);
+            }
+        }
+
+        line_start += line.len();
+    }
+
+    if program.is_empty() && token.offset.is_none() {
+        return Err(ParseError {
+            kind: ParseErrorKind::EmptyProgram,
+            token_index: 0,
+            byte_offset: 0,
+        });
+    }
+
+    program.push(finish_token(&token, program.len(), body.len())?);
+
+    Ok(program)
+}
+
+fn finish_token(token: &Token, token_index: usize, end: usize) -> Result<i128, ParseError> {
+    let Some(byte_offset) = token.offset else {
+        return Err(ParseError {
+            kind: ParseErrorKind::EmptyToken,
+            token_index,
+            byte_offset: end,
+        });
+    };
+
+    let text = token.text.trim_end();
+
+    text.parse().map_err(|_| ParseError {
+        kind: ParseErrorKind::InvalidNumber(text.to_string()),
+        token_index,
+        byte_offset,
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_intcode_program, parse_intcode_program_strict, ParseErrorKind};
+
+    #[test]
+    fn parses_programs() {
+        assert_eq!(
+            parse_intcode_program("1,0,0,0,99"),
+            Ok(vec![1, 0, 0, 0, 99])
+        );
+        assert_eq!(parse_intcode_program("-1,+2\n"), Ok(vec![-1, 2]));
+    }
+
+    #[test]
+    fn accepts_whitespace_and_comments() {
+        let input = "# day 2 example\n1, 9, 10, 3,\n  2,3,11,0 ,\n# trailer\n99,30,40,50\r\n\n";
+        assert_eq!(
+            parse_intcode_program(input),
+            Ok(vec![1, 9, 10, 3, 2, 3, 11, 0, 99, 30, 40, 50])
+        );
+    }
+
+    #[test]
+    fn reports_empty_tokens() {
+        let err = parse_intcode_program("1,,2").unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::EmptyToken);
+        assert_eq!(err.token_index, 1);
+        assert_eq!(err.byte_offset, 2);
+
+        let err = parse_intcode_program("1,2,\n").unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::EmptyToken);
+        assert_eq!(err.token_index, 2);
+        assert_eq!(err.byte_offset, 4);
+    }
+
+    #[test]
+    fn reports_invalid_numbers() {
+        let err = parse_intcode_program("1,3x,2").unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::InvalidNumber("3x".into()));
+        assert_eq!(err.token_index, 1);
+        assert_eq!(err.byte_offset, 2);
+
+        let err = parse_intcode_program("1,2 3").unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::InvalidNumber("2 3".into()));
+    }
+
+    #[test]
+    fn reports_empty_programs() {
+        for input in ["", "\n", " \n# only a comment\n"] {
+            let err = parse_intcode_program(input).unwrap_err();
+            assert_eq!(err.kind, ParseErrorKind::EmptyProgram);
+        }
+    }
+
+    #[test]
+    fn strict_mode_rejects_formatting() {
+        assert_eq!(parse_intcode_program_strict("1,2,99\n"), Ok(vec![1, 2, 99]));
+
+        let err = parse_intcode_program_strict("1, 2").unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::UnexpectedWhitespace);
+        assert_eq!(err.byte_offset, 2);
+
+        let err = parse_intcode_program_strict("# comment\n1,2").unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::UnexpectedComment);
+        assert_eq!(err.byte_offset, 0);
+    }
+}
@@ -0,0 +1,891 @@
+/// Symbolic execution of Intcode programs.
+///
+/// Memory cells and inputs hold expression trees instead of numbers. Arithmetic and comparisons
+/// build new expressions (folding constants along the way), and conditional jumps on symbolic
+/// conditions fork the execution, recording the branch taken as a path constraint.
+///
+/// Values that have to be concrete to continue (instruction words, write addresses, jump targets,
+/// relative base adjustments) are resolved by forking once per assignment of the variables they
+/// depend on. This requires the variables to have a small, bounded domain.
+/// Reads from symbolic addresses before the first write are kept symbolic as loads from the
+/// initial memory, the program image with the cells changed by [`SymbolicComputer::set`].
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, VecDeque};
+use std::error::Error;
+use std::fmt::Display;
+use std::ops::RangeInclusive;
+use std::rc::Rc;
+
+use crate::IntcodeProgram;
+
+/// Maximum number of variable assignments that are enumerated to resolve a value or to check
+/// whether a set of constraints can be satisfied.
+const ENUMERATION_LIMIT: usize = 100_000;
+
+/// A shared, immutable expression.
+pub type Value = Rc<Expr>;
+
+/// A named symbolic input with an optional inclusive domain.
+#[derive(Debug, Clone)]
+pub struct Variable {
+    id: usize,
+    name: Rc<str>,
+    domain: Option<(i128, i128)>,
+}
+
+impl Variable {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The expression referring to this variable.
+    pub fn expr(&self) -> Value {
+        Rc::new(Expr::Var(self.clone()))
+    }
+}
+
+impl PartialEq for Variable {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for Variable {}
+
+impl PartialOrd for Variable {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Variable {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.id.cmp(&other.id)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expr {
+    Const(i128),
+    Var(Variable),
+    /// A read from the initial memory at a symbolic address.
+    Load(Value),
+    Add(Value, Value),
+    Mul(Value, Value),
+    Lt(Value, Value),
+    Eq(Value, Value),
+}
+
+pub fn constant(value: i128) -> Value {
+    Rc::new(Expr::Const(value))
+}
+
+fn add(a: Value, b: Value) -> Value {
+    match (&*a, &*b) {
+        (Expr::Const(x), Expr::Const(y)) => constant(x + y),
+        (Expr::Const(0), _) => b,
+        (_, Expr::Const(0)) => a,
+        _ => Rc::new(Expr::Add(a, b)),
+    }
+}
+
+fn mul(a: Value, b: Value) -> Value {
+    match (&*a, &*b) {
+        (Expr::Const(x), Expr::Const(y)) => constant(x * y),
+        (Expr::Const(0), _) | (_, Expr::Const(0)) => constant(0),
+        (Expr::Const(1), _) => b,
+        (_, Expr::Const(1)) => a,
+        _ => Rc::new(Expr::Mul(a, b)),
+    }
+}
+
+fn lt(a: Value, b: Value) -> Value {
+    match (&*a, &*b) {
+        (Expr::Const(x), Expr::Const(y)) => constant(i128::from(x < y)),
+        _ if a == b => constant(0),
+        _ => Rc::new(Expr::Lt(a, b)),
+    }
+}
+
+fn eq(a: Value, b: Value) -> Value {
+    match (&*a, &*b) {
+        (Expr::Const(x), Expr::Const(y)) => constant(i128::from(x == y)),
+        _ if a == b => constant(1),
+        _ => Rc::new(Expr::Eq(a, b)),
+    }
+}
+
+fn load(address: Value, memory: &[Value]) -> Value {
+    match &*address {
+        Expr::Const(a) => read_memory(memory, *a),
+        _ => Rc::new(Expr::Load(address)),
+    }
+}
+
+fn read_memory(memory: &[Value], address: i128) -> Value {
+    usize::try_from(address)
+        .ok()
+        .and_then(|a| memory.get(a).cloned())
+        .unwrap_or_else(|| constant(0))
+}
+
+impl Expr {
+    pub fn as_const(&self) -> Option<i128> {
+        match self {
+            Expr::Const(x) => Some(*x),
+            _ => None,
+        }
+    }
+
+    /// Evaluates the expression, returns `None` if a variable is not assigned.
+    /// Loads are resolved against `memory`, the initial memory of the program.
+    pub fn eval(&self, env: &BTreeMap<Variable, i128>, memory: &[Value]) -> Option<i128> {
+        Some(match self {
+            Expr::Const(x) => *x,
+            Expr::Var(v) => *env.get(v)?,
+            Expr::Load(a) => read_memory(memory, a.eval(env, memory)?).eval(env, memory)?,
+            Expr::Add(a, b) => a.eval(env, memory)? + b.eval(env, memory)?,
+            Expr::Mul(a, b) => a.eval(env, memory)? * b.eval(env, memory)?,
+            Expr::Lt(a, b) => i128::from(a.eval(env, memory)? < b.eval(env, memory)?),
+            Expr::Eq(a, b) => i128::from(a.eval(env, memory)? == b.eval(env, memory)?),
+        })
+    }
+
+    /// Collects all variables the expression depends on.
+    pub fn variables(&self, out: &mut Vec<Variable>) {
+        match self {
+            Expr::Const(_) => {}
+            Expr::Var(v) => {
+                if !out.contains(v) {
+                    out.push(v.clone());
+                }
+            }
+            Expr::Load(a) => a.variables(out),
+            Expr::Add(a, b) | Expr::Mul(a, b) | Expr::Lt(a, b) | Expr::Eq(a, b) => {
+                a.variables(out);
+                b.variables(out);
+            }
+        }
+    }
+
+    /// Replaces the variables assigned in `env`, folding constants. Loads from addresses that
+    /// become constant are resolved against `memory`, the initial memory of the program.
+    fn substitute(self: &Value, env: &BTreeMap<Variable, i128>, memory: &[Value]) -> Value {
+        match &**self {
+            Expr::Const(_) => Rc::clone(self),
+            Expr::Var(v) => env.get(v).map_or_else(|| Rc::clone(self), |x| constant(*x)),
+            Expr::Load(a) => {
+                let address = a.substitute(env, memory);
+                match address.as_const() {
+                    // the loaded cell may depend on assigned variables as well.
+                    Some(_) => load(address, memory).substitute(env, memory),
+                    None => Rc::new(Expr::Load(address)),
+                }
+            }
+            Expr::Add(a, b) => add(a.substitute(env, memory), b.substitute(env, memory)),
+            Expr::Mul(a, b) => mul(a.substitute(env, memory), b.substitute(env, memory)),
+            Expr::Lt(a, b) => lt(a.substitute(env, memory), b.substitute(env, memory)),
+            Expr::Eq(a, b) => eq(a.substitute(env, memory), b.substitute(env, memory)),
+        }
+    }
+
+    /// Rewrites the expression as `constant + Σ coefficient * variable` if it is linear.
+    pub fn as_linear(&self) -> Option<Linear> {
+        match self {
+            Expr::Const(x) => Some(Linear {
+                terms: BTreeMap::new(),
+                constant: *x,
+            }),
+            Expr::Var(v) => Some(Linear {
+                terms: BTreeMap::from([(v.clone(), 1)]),
+                constant: 0,
+            }),
+            Expr::Add(a, b) => {
+                let mut a = a.as_linear()?;
+                let b = b.as_linear()?;
+
+                for (var, coefficient) in b.terms {
+                    *a.terms.entry(var).or_insert(0) += coefficient;
+                }
+                a.terms.retain(|_, c| *c != 0);
+                a.constant += b.constant;
+
+                Some(a)
+            }
+            Expr::Mul(a, b) => {
+                let (a, b) = (a.as_linear()?, b.as_linear()?);
+                let (factor, mut linear) = match (a.terms.is_empty(), b.terms.is_empty()) {
+                    (true, _) => (a.constant, b),
+                    (_, true) => (b.constant, a),
+                    _ => return None,
+                };
+
+                linear.terms.values_mut().for_each(|c| *c *= factor);
+                linear.terms.retain(|_, c| *c != 0);
+                linear.constant *= factor;
+
+                Some(linear)
+            }
+            Expr::Load(_) | Expr::Lt(_, _) | Expr::Eq(_, _) => None,
+        }
+    }
+}
+
+impl Display for Expr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Expr::Const(x) => write!(f, "{x}"),
+            Expr::Var(v) => write!(f, "{}", v.name),
+            Expr::Load(a) => write!(f, "mem[{a}]"),
+            Expr::Add(a, b) => write!(f, "({a} + {b})"),
+            Expr::Mul(a, b) => write!(f, "({a} * {b})"),
+            Expr::Lt(a, b) => write!(f, "({a} < {b})"),
+            Expr::Eq(a, b) => write!(f, "({a} == {b})"),
+        }
+    }
+}
+
+/// A linear combination of variables.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Linear {
+    pub terms: BTreeMap<Variable, i128>,
+    pub constant: i128,
+}
+
+impl Linear {
+    /// Finds an assignment within the variable domains for which the combination equals `target`.
+    /// All but the last variable are enumerated, the last one is solved for.
+    pub fn solve(&self, target: i128) -> Option<BTreeMap<Variable, i128>> {
+        let mut vars: Vec<Variable> = self.terms.keys().cloned().collect();
+
+        let Some(last) = vars.pop() else {
+            return (self.constant == target).then(BTreeMap::new);
+        };
+
+        let coefficient = self.terms[&last];
+        let (min, max) = last.domain?;
+
+        assignments(&vars)?.into_iter().find_map(|mut env| {
+            let partial: i128 = env.iter().map(|(v, x)| self.terms[v] * x).sum();
+            let remainder = target - self.constant - partial;
+
+            if remainder % coefficient != 0 {
+                return None;
+            }
+
+            let value = remainder / coefficient;
+            if value < min || value > max {
+                return None;
+            }
+
+            env.insert(last.clone(), value);
+            Some(env)
+        })
+    }
+}
+
+/// A branch condition: `expr` is non-zero if `holds` is set, zero otherwise.
+#[derive(Debug, Clone)]
+pub struct Constraint {
+    pub expr: Value,
+    pub holds: bool,
+}
+
+impl Constraint {
+    fn is_satisfied(&self, env: &BTreeMap<Variable, i128>, memory: &[Value]) -> Option<bool> {
+        Some((self.expr.eval(env, memory)? != 0) == self.holds)
+    }
+}
+
+impl Display for Constraint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.holds {
+            write!(f, "{} != 0", self.expr)
+        } else {
+            write!(f, "{} == 0", self.expr)
+        }
+    }
+}
+
+/// A fully explored execution path.
+#[derive(Debug, Clone)]
+pub struct Path {
+    /// Branch conditions that lead to this path.
+    pub constraints: Vec<Constraint>,
+    /// Variables that had to be fixed to a concrete value on this path.
+    pub bindings: Vec<(Variable, i128)>,
+    pub memory: Vec<Value>,
+    pub output: Vec<Value>,
+}
+
+#[derive(Debug)]
+pub enum SymbolicError {
+    /// More paths than allowed by [`SymbolicComputer::set_max_paths`].
+    PathLimit,
+    /// A path exceeded the steps allowed by [`SymbolicComputer::set_max_steps`].
+    StepLimit,
+    /// A value had to be resolved, but depends on variables that cannot be enumerated.
+    Unbounded(Value),
+    InvalidInstruction(i128),
+}
+
+impl Error for SymbolicError {}
+
+impl Display for SymbolicError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SymbolicError::PathLimit => f.write_str("too many execution paths."),
+            SymbolicError::StepLimit => f.write_str("execution path did not halt."),
+            SymbolicError::Unbounded(expr) => {
+                write!(f, "cannot resolve `{expr}` to a concrete value.")
+            }
+            SymbolicError::InvalidInstruction(i) => write!(f, "invalid instruction: {i}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct State {
+    memory: Vec<Value>,
+    instruction_pointer: usize,
+    relative_base: i128,
+    input: VecDeque<Value>,
+    output: Vec<Value>,
+    constraints: Vec<Constraint>,
+    bindings: Vec<(Variable, i128)>,
+    /// Set after the first write, symbolic reads are resolved from then on.
+    has_written: bool,
+    steps: usize,
+}
+
+enum Outcome {
+    Halted(State),
+    Forked(Vec<State>),
+}
+
+/// Explores every execution path of a program with symbolic memory and inputs.
+///
+/// ```ignore
+/// let mut machine = SymbolicComputer::new(&program);
+/// let noun = machine.variable("noun", 0..=99);
+/// machine.set(1, noun.expr());
+/// let paths = machine.explore()?;
+/// ```
+pub struct SymbolicComputer {
+    initial: State,
+    variables: usize,
+    input_domain: Option<(i128, i128)>,
+    max_paths: usize,
+    max_steps: usize,
+}
+
+impl SymbolicComputer {
+    pub fn new(program: &IntcodeProgram) -> Self {
+        Self {
+            initial: State {
+                memory: program.iter().map(|x| constant(*x)).collect(),
+                instruction_pointer: 0,
+                relative_base: 0,
+                input: VecDeque::new(),
+                output: vec![],
+                constraints: vec![],
+                bindings: vec![],
+                has_written: false,
+                steps: 0,
+            },
+            variables: 0,
+            input_domain: None,
+            max_paths: 1000,
+            max_steps: 1_000_000,
+        }
+    }
+
+    /// Creates a new variable with the given domain.
+    pub fn variable(&mut self, name: &str, domain: RangeInclusive<i128>) -> Variable {
+        self.new_variable(name, Some((*domain.start(), *domain.end())))
+    }
+
+    fn new_variable(&mut self, name: &str, domain: Option<(i128, i128)>) -> Variable {
+        self.variables += 1;
+
+        Variable {
+            id: self.variables,
+            name: name.into(),
+            domain,
+        }
+    }
+
+    pub fn set(&mut self, address: usize, value: Value) {
+        if address >= self.initial.memory.len() {
+            self.initial.memory.resize(address + 1, constant(0));
+        }
+
+        self.initial.memory[address] = value;
+    }
+
+    pub fn set_input(&mut self, value: Value) {
+        self.initial.input.push_back(value);
+    }
+
+    /// Domain of the variables created for reads from an empty input queue.
+    /// Without a domain, these inputs cannot be resolved to concrete values.
+    pub fn set_input_domain(&mut self, domain: RangeInclusive<i128>) {
+        self.input_domain = Some((*domain.start(), *domain.end()));
+    }
+
+    pub fn set_max_paths(&mut self, max_paths: usize) {
+        self.max_paths = max_paths;
+    }
+
+    pub fn set_max_steps(&mut self, max_steps: usize) {
+        self.max_steps = max_steps;
+    }
+
+    /// Runs the program until every feasible path has halted.
+    pub fn explore(&mut self) -> Result<Vec<Path>, SymbolicError> {
+        let mut pending = vec![self.initial.clone()];
+        let mut paths = vec![];
+
+        while let Some(state) = pending.pop() {
+            match self.run(state)? {
+                Outcome::Halted(state) => paths.push(Path {
+                    constraints: state.constraints,
+                    bindings: state.bindings,
+                    memory: state.memory,
+                    output: state.output,
+                }),
+                Outcome::Forked(states) => pending.extend(states),
+            }
+
+            if paths.len() + pending.len() > self.max_paths {
+                return Err(SymbolicError::PathLimit);
+            }
+        }
+
+        Ok(paths)
+    }
+
+    fn run(&mut self, mut s: State) -> Result<Outcome, SymbolicError> {
+        loop {
+            s.steps += 1;
+            if s.steps > self.max_steps {
+                return Err(SymbolicError::StepLimit);
+            }
+
+            let word = get(&s, s.instruction_pointer);
+            let Some(instruction) = word.as_const() else {
+                return self.fork_on(&s, &word);
+            };
+
+            let outcome = match instruction % 100 {
+                op @ (1 | 2 | 7 | 8) => {
+                    let a = self.read_parameter(&s, 1, instruction)?;
+                    let b = self.read_parameter(&s, 2, instruction)?;
+                    let (a, b) = match (a, b) {
+                        (Ok(a), Ok(b)) => (a, b),
+                        (Err(forks), _) | (_, Err(forks)) => return Ok(forks),
+                    };
+
+                    let result = match op {
+                        1 => add(a, b),
+                        2 => mul(a, b),
+                        7 => lt(a, b),
+                        _ => eq(a, b),
+                    };
+
+                    self.write(&mut s, 3, instruction, result, 4)?
+                }
+                // resolve the address first, forked states re-execute the instruction.
+                3 => match self.address(&s, 1, instruction)? {
+                    Ok(address) => {
+                        let value = match s.input.pop_front() {
+                            Some(value) => value,
+                            None => {
+                                let name = format!("input{}", self.variables + 1);
+                                self.new_variable(&name, self.input_domain).expr()
+                            }
+                        };
+
+                        store(&mut s, address, value, 2);
+                        None
+                    }
+                    Err(forks) => Some(forks),
+                },
+                4 => match self.read_parameter(&s, 1, instruction)? {
+                    Ok(value) => {
+                        s.output.push(value);
+                        s.instruction_pointer += 2;
+                        None
+                    }
+                    Err(forks) => Some(forks),
+                },
+                op @ (5 | 6) => self.jump(&mut s, instruction, op == 5)?,
+                9 => match self.read_parameter(&s, 1, instruction)? {
+                    Ok(value) => match value.as_const() {
+                        Some(offset) => {
+                            s.relative_base += offset;
+                            s.instruction_pointer += 2;
+                            None
+                        }
+                        None => Some(self.fork_on(&s, &value)?),
+                    },
+                    Err(forks) => Some(forks),
+                },
+                99 => return Ok(Outcome::Halted(s)),
+                _ => return Err(SymbolicError::InvalidInstruction(instruction)),
+            };
+
+            if let Some(outcome) = outcome {
+                return Ok(outcome);
+            }
+        }
+    }
+
+    /// Resolves the address of a parameter. Returns the forks if it depends on variables.
+    fn address(
+        &self,
+        s: &State,
+        parameter: usize,
+        instruction: i128,
+    ) -> Result<Result<usize, Outcome>, SymbolicError> {
+        let raw = get(s, s.instruction_pointer + parameter);
+        let address = match mode(instruction, parameter) {
+            0 => raw,
+            2 => add(constant(s.relative_base), raw),
+            _ => return Err(SymbolicError::InvalidInstruction(instruction)),
+        };
+
+        match address.as_const() {
+            Some(a) => Ok(Ok(a as usize)),
+            None => Ok(Err(self.fork_on(s, &address)?)),
+        }
+    }
+
+    fn read_parameter(
+        &self,
+        s: &State,
+        parameter: usize,
+        instruction: i128,
+    ) -> Result<Result<Value, Outcome>, SymbolicError> {
+        let raw = get(s, s.instruction_pointer + parameter);
+
+        let address = match mode(instruction, parameter) {
+            0 => raw,
+            1 => return Ok(Ok(raw)),
+            2 => add(constant(s.relative_base), raw),
+            _ => return Err(SymbolicError::InvalidInstruction(instruction)),
+        };
+
+        match address.as_const() {
+            Some(a) => Ok(Ok(get(s, a as usize))),
+            None if !s.has_written => Ok(Ok(load(address, &self.initial.memory))),
+            None => Ok(Err(self.fork_on(s, &address)?)),
+        }
+    }
+
+    fn write(
+        &self,
+        s: &mut State,
+        parameter: usize,
+        instruction: i128,
+        value: Value,
+        steps: usize,
+    ) -> Result<Option<Outcome>, SymbolicError> {
+        let address = match self.address(s, parameter, instruction)? {
+            Ok(address) => address,
+            Err(forks) => return Ok(Some(forks)),
+        };
+
+        store(s, address, value, steps);
+
+        Ok(None)
+    }
+
+    fn jump(
+        &self,
+        s: &mut State,
+        instruction: i128,
+        if_true: bool,
+    ) -> Result<Option<Outcome>, SymbolicError> {
+        let condition = match self.read_parameter(s, 1, instruction)? {
+            Ok(value) => value,
+            Err(forks) => return Ok(Some(forks)),
+        };
+        let target = match self.read_parameter(s, 2, instruction)? {
+            Ok(value) => value,
+            Err(forks) => return Ok(Some(forks)),
+        };
+
+        let Some(condition) = condition.as_const() else {
+            // resolve the target first, the jump is re-executed in the forked states.
+            let Some(target) = target.as_const() else {
+                return Ok(Some(self.fork_on(s, &target)?));
+            };
+
+            let mut taken = s.clone();
+            taken.instruction_pointer = target as usize;
+            taken.constraints.push(Constraint {
+                expr: Rc::clone(&condition),
+                holds: if_true,
+            });
+
+            let mut skipped = s.clone();
+            skipped.instruction_pointer += 3;
+            skipped.constraints.push(Constraint {
+                expr: condition,
+                holds: !if_true,
+            });
+
+            let forks = [taken, skipped]
+                .into_iter()
+                .filter(|state| self.is_feasible(state))
+                .collect();
+
+            return Ok(Some(Outcome::Forked(forks)));
+        };
+
+        if (condition != 0) != if_true {
+            s.instruction_pointer += 3;
+            return Ok(None);
+        }
+
+        match target.as_const() {
+            Some(t) => {
+                s.instruction_pointer = t as usize;
+                Ok(None)
+            }
+            None => Ok(Some(self.fork_on(s, &target)?)),
+        }
+    }
+
+    /// Forks the state once per assignment of the variables `value` depends on, substituting
+    /// the assigned values everywhere. The forked states re-execute the current instruction.
+    fn fork_on(&self, s: &State, value: &Value) -> Result<Outcome, SymbolicError> {
+        let mut vars = vec![];
+        value.variables(&mut vars);
+
+        let envs = assignments(&vars).ok_or_else(|| SymbolicError::Unbounded(value.clone()))?;
+
+        let mut forks = vec![];
+
+        for env in envs {
+            let mut state = s.clone();
+
+            state
+                .bindings
+                .extend(env.iter().map(|(var, x)| (var.clone(), *x)));
+
+            // loads resolved now may read cells that depend on variables bound in earlier forks.
+            let bound: BTreeMap<Variable, i128> = state.bindings.iter().cloned().collect();
+            let subst = |v: &Value| v.substitute(&bound, &self.initial.memory);
+
+            state.memory = state.memory.iter().map(subst).collect();
+            state.input = state.input.iter().map(subst).collect();
+            state.output = state.output.iter().map(subst).collect();
+            state.constraints = state
+                .constraints
+                .iter()
+                .map(|c| Constraint {
+                    expr: subst(&c.expr),
+                    holds: c.holds,
+                })
+                .collect();
+
+            // drop constraints that became constant, and the whole state if one of them fails.
+            let mut feasible = true;
+            state.constraints.retain(|c| match c.expr.as_const() {
+                Some(x) => {
+                    feasible &= (x != 0) == c.holds;
+                    false
+                }
+                None => true,
+            });
+
+            if feasible && self.is_feasible(&state) {
+                forks.push(state);
+            }
+
+            if forks.len() > self.max_paths {
+                return Err(SymbolicError::PathLimit);
+            }
+        }
+
+        Ok(Outcome::Forked(forks))
+    }
+
+    /// Checks whether some assignment satisfies all constraints.
+    /// Assumes feasibility if the variables cannot be enumerated.
+    fn is_feasible(&self, s: &State) -> bool {
+        let mut vars = vec![];
+        s.constraints
+            .iter()
+            .for_each(|c| c.expr.variables(&mut vars));
+
+        let Some(envs) = assignments(&vars) else {
+            return true;
+        };
+
+        envs.iter().any(|env| {
+            s.constraints
+                .iter()
+                .all(|c| c.is_satisfied(env, &self.initial.memory).unwrap_or(true))
+        })
+    }
+}
+
+fn store(s: &mut State, address: usize, value: Value, steps: usize) {
+    if address >= s.memory.len() {
+        s.memory.resize(address + 1, constant(0));
+    }
+
+    s.memory[address] = value;
+    s.has_written = true;
+    s.instruction_pointer += steps;
+}
+
+fn get(s: &State, address: usize) -> Value {
+    s.memory
+        .get(address)
+        .cloned()
+        .unwrap_or_else(|| constant(0))
+}
+
+fn mode(instruction: i128, parameter: usize) -> usize {
+    instruction as usize / 10_usize.pow(parameter as u32 + 1) % 10
+}
+
+/// Enumerates every assignment of `vars` within their domains.
+/// Returns `None` if a domain is unbounded or there are too many assignments.
+fn assignments(vars: &[Variable]) -> Option<Vec<BTreeMap<Variable, i128>>> {
+    let mut count: usize = 1;
+    for var in vars {
+        let (min, max) = var.domain?;
+        let size = usize::try_from(max - min + 1).ok()?;
+        count = count.checked_mul(size)?;
+    }
+
+    if count > ENUMERATION_LIMIT {
+        return None;
+    }
+
+    let mut envs = vec![BTreeMap::new()];
+
+    for var in vars {
+        let (min, max) = var.domain?;
+        envs = envs
+            .into_iter()
+            .flat_map(|env| {
+                (min..=max).map(move |x| {
+                    let mut env = env.clone();
+                    env.insert(var.clone(), x);
+                    env
+                })
+            })
+            .collect();
+    }
+
+    Some(envs)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::{constant, SymbolicComputer};
+
+    #[test]
+    fn solves_noun_verb_programs() {
+        // mem[0] = noun * 1000 + verb + 7, with a load from the symbolic addresses up front.
+        let program = vec![
+            1, 0, 0, 3, 2, 1, 20, 0, 1, 0, 2, 0, 1, 0, 21, 0, 99, 0, 0, 0, 1000, 7,
+        ];
+
+        let mut machine = SymbolicComputer::new(&program);
+        let noun = machine.variable("noun", 0..=99);
+        let verb = machine.variable("verb", 0..=99);
+        machine.set(1, noun.expr());
+        machine.set(2, verb.expr());
+
+        let paths = machine.explore().unwrap();
+        assert_eq!(paths.len(), 1);
+        assert!(paths[0].constraints.is_empty());
+
+        let result = &paths[0].memory[0];
+        assert_eq!(result.to_string(), "(((noun * 1000) + verb) + 7)");
+
+        let solution = result.as_linear().unwrap().solve(12012).unwrap();
+        assert_eq!(solution, BTreeMap::from([(noun, 12), (verb, 5)]));
+    }
+
+    #[test]
+    fn forks_on_symbolic_branches() {
+        // outputs 1 if the input equals 8, 0 otherwise.
+        let program = vec![
+            3, 17, 8, 17, 18, 17, 1006, 17, 14, 104, 1, 1105, 1, 16, 104, 0, 99, 0, 8,
+        ];
+
+        let mut machine = SymbolicComputer::new(&program);
+        let paths = machine.explore().unwrap();
+
+        assert_eq!(paths.len(), 2);
+        for path in paths {
+            let [constraint] = path.constraints.as_slice() else {
+                panic!("expected a single constraint");
+            };
+            assert_eq!(constraint.expr.to_string(), "(input1 == 8)");
+
+            let expected = if constraint.holds { 1 } else { 0 };
+            assert_eq!(path.output, vec![constant(expected)]);
+        }
+    }
+
+    #[test]
+    fn loads_from_patched_memory() {
+        // mem[7] = mem[x] + mem[21], then writes 2 to the address in mem[7].
+        let program = vec![
+            1, 20, 21, 7, 1101, 1, 1, 0, 99, 0, 16, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+
+        let mut machine = SymbolicComputer::new(&program);
+        let x = machine.variable("x", 10..=11);
+        machine.set(1, x.expr());
+        machine.set(11, constant(15));
+
+        let paths = machine.explore().unwrap();
+        assert_eq!(paths.len(), 2);
+
+        for path in paths {
+            let [(var, value)] = path.bindings.as_slice() else {
+                panic!("expected a single binding");
+            };
+            assert_eq!(var, &x);
+
+            let target = if *value == 10 { 16 } else { 15 };
+            assert_eq!(path.memory[target], constant(2));
+        }
+    }
+
+    #[test]
+    fn resolves_symbolic_addresses() {
+        // writes the input to address `x`.
+        let program = vec![3, 0, 99];
+
+        let mut machine = SymbolicComputer::new(&program);
+        let x = machine.variable("x", 10..=11);
+        machine.set(1, x.expr());
+        machine.set_input(constant(5));
+
+        let paths = machine.explore().unwrap();
+        assert_eq!(paths.len(), 2);
+
+        for path in paths {
+            let [(var, address)] = path.bindings.as_slice() else {
+                panic!("expected a single binding");
+            };
+            assert_eq!(var, &x);
+            assert_eq!(path.memory[*address as usize], constant(5));
+        }
+    }
+}
@@ -1,7 +1,17 @@
 use std::collections::VecDeque;
+use std::sync::Mutex;
 
+use intcode::compiled::CodeCache;
+
+pub mod intcode;
 pub mod template;
 
+pub use intcode::binary::{decode_program, encode_program, BinaryFormatError};
+pub use intcode::compiled::Engine;
+pub use intcode::parser::{
+    parse_intcode_program, parse_intcode_program_strict, ParseError, ParseErrorKind,
+};
+
 // Use this file to add helper functions and additional modules.
 
 pub enum ParameterMode {
@@ -12,6 +22,37 @@ pub enum ParameterMode {
 
 pub type IntcodeProgram = Vec<i128>;
 
+/// A parsed program together with the text it was parsed from.
+/// Reloading the same text reuses the image instead of parsing it again.
+#[derive(Debug, Clone)]
+struct ProgramImage {
+    source: String,
+    program: IntcodeProgram,
+}
+
+/// Programs decoded from binary images, see [`cache_program_image`].
+static PROGRAM_IMAGES: Mutex<Vec<ProgramImage>> = Mutex::new(Vec::new());
+
+/// Adds a program decoded from a binary image to the parse cache, so that loading its text with
+/// [`load_intcode_program`] returns the program instead of parsing the text again.
+pub fn cache_program_image(source: String, program: IntcodeProgram) {
+    let mut images = PROGRAM_IMAGES.lock().unwrap_or_else(|e| e.into_inner());
+
+    if !images.iter().any(|image| image.source == source) {
+        images.push(ProgramImage { source, program });
+    }
+}
+
+/// Parses a program, or returns it from the parse cache if it was decoded from a binary image.
+pub fn load_intcode_program(input: &str) -> Result<IntcodeProgram, ParseError> {
+    let images = PROGRAM_IMAGES.lock().unwrap_or_else(|e| e.into_inner());
+
+    match images.iter().find(|image| image.source == input) {
+        Some(image) => Ok(image.program.clone()),
+        None => parse_intcode_program(input),
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct IntcodeComputer {
     instruction_pointer: usize,
@@ -23,6 +64,9 @@ pub struct IntcodeComputer {
     relative_base: i128,
     default_input: Option<i128>,
     input_requested: bool,
+    image: Option<ProgramImage>,
+    engine: Engine,
+    code: CodeCache,
 }
 
 impl Default for IntcodeComputer {
@@ -43,6 +87,9 @@ impl IntcodeComputer {
             relative_base: 0,
             default_input: None,
             input_requested: false,
+            image: None,
+            engine: Engine::from_env(),
+            code: CodeCache::default(),
         }
     }
 
@@ -54,19 +101,44 @@ impl IntcodeComputer {
         self.ticks
     }
 
+    pub fn engine(&self) -> Engine {
+        self.engine
+    }
+
+    pub fn set_engine(&mut self, engine: Engine) {
+        self.engine = engine;
+    }
+
     pub fn reset(&mut self) {
         self.instruction_pointer = 0;
         self.is_running = true;
     }
 
-    pub fn load_program_from_str(&mut self, input: &str) {
-        if let Some(program) = parse_intcode_program(input) {
-            self.load_program(&program);
+    pub fn load_program_from_str(&mut self, input: &str) -> Result<(), ParseError> {
+        if let Some(image) = &self.image {
+            if image.source == input {
+                self.memory.clone_from(&image.program);
+                self.code = CodeCache::default();
+                self.reset();
+
+                return Ok(());
+            }
         }
+
+        let program = load_intcode_program(input)?;
+        self.load_program(&program);
+
+        self.image = Some(ProgramImage {
+            source: input.to_string(),
+            program,
+        });
+
+        Ok(())
     }
 
     pub fn load_program(&mut self, program: &IntcodeProgram) {
         self.memory = program.clone();
+        self.code = CodeCache::default();
 
         self.reset();
     }
@@ -89,6 +161,7 @@ impl IntcodeComputer {
         }
 
         self.memory[address] = val;
+        self.code.notify_write(address);
     }
 
     pub fn read_program(&self) -> IntcodeProgram {
@@ -99,6 +172,17 @@ impl IntcodeComputer {
         !self.input.is_empty()
     }
 
+    /// Returns `true` if the next instruction reads input, but none is available.
+    pub fn awaits_input(&self) -> bool {
+        self.is_running
+            && self.input.is_empty()
+            && self.default_input.is_none()
+            && self
+                .memory
+                .get(self.instruction_pointer)
+                .is_some_and(|instruction| self.opcode(*instruction) == 3)
+    }
+
     pub fn set_input(&mut self, val: i128) {
         self.input.push_back(val);
     }
@@ -139,6 +223,11 @@ impl IntcodeComputer {
         !self.output.is_empty()
     }
 
+    /// Removes all output values, in the order they were produced.
+    pub fn take_output(&mut self) -> Vec<i128> {
+        std::mem::take(&mut self.output)
+    }
+
     fn parameter_mode(&self, instruction: i128, parameter: i8) -> ParameterMode {
         let mode = instruction as usize / (10_usize.pow(parameter as u32 + 1)) % 10;
 
@@ -203,9 +292,17 @@ impl IntcodeComputer {
         self.instruction_pointer += steps;
     }
 
+    /// Advances execution by one instruction, or by a whole block with the compiled engine.
+    fn step(&mut self) {
+        match self.engine {
+            Engine::Interpreter => self.tick(),
+            Engine::Compiled => self.step_compiled(),
+        }
+    }
+
     pub fn run_until_output(&mut self) -> Option<i128> {
         while !self.has_output() {
-            self.tick();
+            self.step();
         }
 
         self.input_requested = false;
@@ -215,7 +312,7 @@ impl IntcodeComputer {
 
     pub fn run_until_io(&mut self) {
         while !self.has_output() && !self.input_requested {
-            self.tick();
+            self.step();
         }
 
         self.input_requested = false;
@@ -223,7 +320,7 @@ impl IntcodeComputer {
 
     pub fn run(&mut self) {
         while self.is_running {
-            self.tick();
+            self.step();
         }
 
         self.input_requested = false;
@@ -333,11 +430,48 @@ impl IntcodeComputer {
     }
 }
 
-pub fn parse_intcode_program(input: &str) -> Option<IntcodeProgram> {
-    Some(
-        input
-            .split(',')
-            .filter_map(|n| n.trim().parse().ok())
-            .collect(),
-    )
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{cache_program_image, load_intcode_program, IntcodeComputer};
+
+    #[test]
+    fn reloads_cached_program_image() {
+        let mut computer = IntcodeComputer::new();
+
+        computer.load_program_from_str("1,0,0,0,99").unwrap();
+        computer.set(0, 7);
+        computer.load_program_from_str("1,0,0,0,99").unwrap();
+
+        assert_eq!(computer.memory_snapshot(), &[1, 0, 0, 0, 99]);
+        assert_eq!(
+            computer.image.as_ref().unwrap().program,
+            vec![1, 0, 0, 0, 99]
+        );
+
+        computer.load_program_from_str("2,0,0,0,99").unwrap();
+
+        assert_eq!(computer.memory_snapshot(), &[2, 0, 0, 0, 99]);
+        assert_eq!(computer.image.as_ref().unwrap().source, "2,0,0,0,99");
+        assert!(computer.load_program_from_str("1,x").is_err());
+    }
+
+    #[test]
+    fn loads_cached_images_without_parsing() {
+        cache_program_image("104,1,99,-1".into(), vec![104, 2, 99]);
+
+        assert_eq!(
+            load_intcode_program("104,1,99,-1").unwrap(),
+            vec![104, 2, 99]
+        );
+        assert_eq!(load_intcode_program("104,1,99").unwrap(), vec![104, 1, 99]);
+
+        let mut computer = IntcodeComputer::new();
+        computer.load_program_from_str("104,1,99,-1").unwrap();
+
+        assert_eq!(computer.memory_snapshot(), &[104, 2, 99]);
+    }
 }
+
+/* -------------------------------------------------------------------------- */
@@ -1,39 +1,79 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, convert, download, list, read, scaffold, solve, time, verify,
+};
+use advent_of_code::template::registry;
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
+use advent_of_code::template::{templates, Puzzle};
 #[cfg(feature = "today")]
 use std::process;
 
+/// Solutions of all days, linked into this binary. Generated by `build.rs`.
+mod solutions {
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
+
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::commands::solve::InputOptions;
+    use advent_of_code::template::commands::time::{BaselineOptions, TimeOptions};
+    use advent_of_code::template::runner::parse_timeout;
+    use advent_of_code::template::templates;
+    use advent_of_code::template::{Day, Puzzle, Year};
+    use std::time::Duration;
+    use std::{process, thread};
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            puzzle: Puzzle,
+            force: bool,
         },
         Read {
-            day: Day,
+            puzzle: Puzzle,
         },
         Scaffold {
-            day: Day,
+            puzzle: Puzzle,
             download: bool,
+            force: bool,
+            template: String,
         },
         Solve {
-            day: Day,
+            puzzle: Puzzle,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            timeout: Option<Duration>,
+            inputs: InputOptions,
         },
         All {
+            year: Year,
             release: bool,
+            spawn: bool,
+            jobs: usize,
+            timeout: Option<Duration>,
         },
         Time {
-            all: bool,
+            year: Year,
+            day: Option<Day>,
+            history: bool,
+            options: TimeOptions,
+            baseline: BaselineOptions,
+        },
+        Verify {
+            year: Year,
             day: Option<Day>,
-            store: bool,
+            spawn: bool,
+            jobs: usize,
+            timeout: Option<Duration>,
+            record: bool,
+            strict: bool,
+        },
+        Convert {
+            puzzle: Puzzle,
+        },
+        List {
+            year: Year,
+            tests: bool,
         },
         #[cfg(feature = "today")]
         Today,
@@ -42,35 +82,100 @@ mod args {
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
-        let app_args = match args.subcommand()?.as_deref() {
+        let subcommand = args.subcommand()?;
+
+        // days without a year (`DD`) refer to `--year`, which defaults to the primary year.
+        let year = args
+            .opt_value_from_str("--year")?
+            .unwrap_or_else(Year::primary);
+
+        let app_args = match subcommand.as_deref() {
             Some("all") => AppArguments::All {
+                year,
                 release: args.contains("--release"),
+                spawn: args.contains("--spawn"),
+                jobs: parse_jobs(&mut args)?,
+                timeout: args.opt_value_from_fn("--timeout", parse_timeout)?,
             },
             Some("time") => {
-                let all = args.contains("--all");
-                let store = args.contains("--store");
+                let history = args.contains("--history");
+
+                let options = TimeOptions {
+                    all: args.contains("--all"),
+                    store: args.contains("--store"),
+                    html: args.contains("--html"),
+                    spawn: args.contains("--spawn"),
+                    timeout: args.opt_value_from_fn("--timeout", parse_timeout)?,
+                    memory: args.contains("--memory"),
+                };
+
+                let baseline = BaselineOptions {
+                    compare_to: args.opt_value_from_str("--baseline")?,
+                    save_as: args.opt_value_from_str("--save-baseline")?,
+                    threshold: args.opt_value_from_str("--threshold")?,
+                };
+
+                let puzzle = parse_opt_puzzle(&mut args, year)?;
 
                 AppArguments::Time {
-                    all,
-                    day: args.opt_free_from_str()?,
-                    store,
+                    year: puzzle.map_or(year, |p| p.year),
+                    day: puzzle.map(|p| p.day),
+                    history,
+                    options,
+                    baseline,
+                }
+            }
+            Some("verify") => {
+                let spawn = args.contains("--spawn");
+                let jobs = parse_jobs(&mut args)?;
+                let timeout = args.opt_value_from_fn("--timeout", parse_timeout)?;
+                let record = args.contains("--record");
+                let strict = args.contains("--strict");
+                let puzzle = parse_opt_puzzle(&mut args, year)?;
+
+                AppArguments::Verify {
+                    year: puzzle.map_or(year, |p| p.year),
+                    day: puzzle.map(|p| p.day),
+                    spawn,
+                    jobs,
+                    timeout,
+                    record,
+                    strict,
                 }
             }
+            Some("convert") => AppArguments::Convert {
+                puzzle: parse_puzzle(&mut args, year)?,
+            },
+            Some("list") => AppArguments::List {
+                year,
+                tests: args.contains("--tests"),
+            },
             Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                puzzle: parse_puzzle(&mut args, year)?,
+                force: args.contains("--force"),
             },
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+                puzzle: parse_puzzle(&mut args, year)?,
             },
             Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+                puzzle: parse_puzzle(&mut args, year)?,
                 download: args.contains("--download"),
+                force: args.contains("--force"),
+                template: args
+                    .opt_value_from_str("--template")?
+                    .unwrap_or_else(|| templates::DEFAULT_TEMPLATE.into()),
             },
             Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
+                puzzle: parse_puzzle(&mut args, year)?,
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 dhat: args.contains("--dhat"),
+                timeout: args.opt_value_from_fn("--timeout", parse_timeout)?,
+                inputs: InputOptions {
+                    input: args.opt_value_from_str("--input")?,
+                    input_set: args.opt_value_from_str("--input-set")?,
+                    all_inputs: args.contains("--all-inputs"),
+                },
             },
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
@@ -91,38 +196,104 @@ mod args {
 
         Ok(app_args)
     }
+
+    /// Parses a puzzle argument, `DD` for a day of `year` or `YYYY-DD`.
+    fn parse_puzzle(
+        args: &mut pico_args::Arguments,
+        year: Year,
+    ) -> Result<Puzzle, Box<dyn std::error::Error>> {
+        let puzzle: String = args.free_from_str()?;
+        Ok(Puzzle::parse(&puzzle, year)?)
+    }
+
+    /// Like [`parse_puzzle`], for commands that run all days of a year if no puzzle is given.
+    fn parse_opt_puzzle(
+        args: &mut pico_args::Arguments,
+        year: Year,
+    ) -> Result<Option<Puzzle>, Box<dyn std::error::Error>> {
+        let puzzle: Option<String> = args.opt_free_from_str()?;
+        Ok(puzzle.map(|p| Puzzle::parse(&p, year)).transpose()?)
+    }
+
+    /// Parses `--jobs N`. `--jobs 0` uses all available cores.
+    fn parse_jobs(args: &mut pico_args::Arguments) -> Result<usize, pico_args::Error> {
+        Ok(match args.opt_value_from_str("--jobs")? {
+            Some(0) => thread::available_parallelism().map_or(1, usize::from),
+            Some(jobs) => jobs,
+            None => 1,
+        })
+    }
 }
 
 fn main() {
+    registry::install(solutions::SOLUTIONS);
+
     match parse() {
         Err(err) => {
             eprintln!("Error: {err}");
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day, download } => {
-                scaffold::handle(day);
+            AppArguments::All {
+                year,
+                release,
+                spawn,
+                jobs,
+                timeout,
+            } => all::handle(year, release, spawn, jobs, timeout),
+            AppArguments::Time {
+                year,
+                day,
+                history,
+                options,
+                baseline,
+            } => {
+                if history {
+                    time::print_history(year, day);
+                } else {
+                    time::handle(year, day, &options, &baseline);
+                }
+            }
+            AppArguments::Verify {
+                year,
+                day,
+                spawn,
+                jobs,
+                timeout,
+                record,
+                strict,
+            } => verify::handle(year, day, spawn, jobs, timeout, record, strict),
+            AppArguments::Convert { puzzle } => convert::handle(puzzle),
+            AppArguments::List { year, tests } => list::handle(year, tests),
+            AppArguments::Download { puzzle, force } => download::handle(puzzle, force),
+            AppArguments::Read { puzzle } => read::handle(puzzle),
+            AppArguments::Scaffold {
+                puzzle,
+                download,
+                force,
+                template,
+            } => {
+                // downloading first lets the scaffold take its examples from the puzzle.
                 if download {
-                    download::handle(day);
+                    download::handle(puzzle, force);
                 }
+                scaffold::handle(puzzle, &template);
             }
             AppArguments::Solve {
-                day,
+                puzzle,
                 release,
                 dhat,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                timeout,
+                inputs,
+            } => solve::handle(puzzle, release, dhat, submit, timeout, inputs),
             #[cfg(feature = "today")]
             AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day);
-                        download::handle(day);
-                        read::handle(day)
+                match Puzzle::today() {
+                    Some(puzzle) => {
+                        download::handle(puzzle, false);
+                        scaffold::handle(puzzle, templates::DEFAULT_TEMPLATE);
+                        read::handle(puzzle)
                     }
                     None => {
                         eprintln!(
@@ -0,0 +1,195 @@
+/// Accepted answers, stored in `data/answers/DD.txt` (`data/YYYY/answers/DD.txt` for other years).
+/// Answers for named input sets are stored in `data/answers/<name>/DD.txt`.
+///
+/// Answers can span multiple lines (e.g. text rendered as ASCII art), so every part is stored in
+/// its own section:
+///
+/// ```text
+/// part 1:
+/// 1234
+/// part 2:
+/// #..#
+/// ####
+/// ```
+use std::{fs, io};
+
+use crate::template::Puzzle;
+
+const HEADER_PREFIX: &str = "part ";
+
+#[must_use]
+pub fn get_path_for_answers(puzzle: Puzzle) -> String {
+    puzzle.data_path("answers", "txt")
+}
+
+/// Answers for the named input set `name`, see [`crate::template::inputs`].
+#[must_use]
+pub fn get_path_for_named_answers(puzzle: Puzzle, name: &str) -> String {
+    format!(
+        "{}/answers/{name}/{}.txt",
+        puzzle.year.data_dir(),
+        puzzle.day
+    )
+}
+
+/// The accepted answers of a single day.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+impl Answers {
+    /// Reads the answers of `puzzle`. Returns empty answers if none were recorded yet.
+    pub fn read(puzzle: Puzzle) -> io::Result<Self> {
+        Self::read_path(&get_path_for_answers(puzzle))
+    }
+
+    /// Reads answers from a file at `path`. Returns empty answers if the file does not exist.
+    pub fn read_path(path: &str) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(s) => Ok(Self::parse(&s)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn store(&self, puzzle: Puzzle) -> io::Result<()> {
+        fs::create_dir_all(format!("{}/answers", puzzle.year.data_dir()))?;
+        fs::write(get_path_for_answers(puzzle), self.to_file_string())
+    }
+
+    pub fn get(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn set(&mut self, part: u8, answer: &str) {
+        let answer = Some(answer.trim_end_matches('\n').to_string());
+
+        match part {
+            1 => self.part_1 = answer,
+            2 => self.part_2 = answer,
+            _ => {}
+        }
+    }
+
+    fn parse(s: &str) -> Self {
+        let mut answers = Answers::default();
+        let mut current: Option<(u8, Vec<&str>)> = None;
+
+        let mut finish = |section: Option<(u8, Vec<&str>)>| {
+            if let Some((part, lines)) = section {
+                let answer = lines.join("\n");
+                if !answer.trim().is_empty() {
+                    answers.set(part, &answer);
+                }
+            }
+        };
+
+        for line in s.lines() {
+            let header = line
+                .strip_prefix(HEADER_PREFIX)
+                .and_then(|rest| rest.strip_suffix(':'))
+                .and_then(|part| part.parse::<u8>().ok());
+
+            match header {
+                Some(part) => finish(current.replace((part, vec![]))),
+                None => {
+                    if let Some((_, lines)) = current.as_mut() {
+                        lines.push(line);
+                    }
+                }
+            }
+        }
+
+        finish(current);
+        answers
+    }
+
+    fn to_file_string(&self) -> String {
+        let mut s = String::new();
+
+        for part in [1, 2] {
+            if let Some(answer) = self.get(part) {
+                s.push_str(&format!("{HEADER_PREFIX}{part}:\n{answer}\n"));
+            }
+        }
+
+        s
+    }
+}
+
+/// Records an accepted answer for a part, keeping the answer of the other part.
+pub fn record(puzzle: Puzzle, part: u8, answer: &str) -> io::Result<()> {
+    let mut answers = Answers::read(puzzle)?;
+    answers.set(part, answer);
+    answers.store(puzzle)
+}
+
+/// How a result compares to the recorded answer of a part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail,
+    /// No answer was recorded for the part.
+    Missing,
+}
+
+#[must_use]
+pub fn verdict(expected: Option<&str>, actual: Option<&str>) -> Verdict {
+    match (expected, actual) {
+        (None, _) => Verdict::Missing,
+        (Some(expected), Some(actual)) if expected == actual.trim_end_matches('\n') => {
+            Verdict::Pass
+        }
+        _ => Verdict::Fail,
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{verdict, Answers, Verdict};
+
+    #[test]
+    fn compares_answers() {
+        assert_eq!(verdict(Some("42"), Some("42")), Verdict::Pass);
+        assert_eq!(verdict(Some("#.\n.#"), Some("#.\n.#\n")), Verdict::Pass);
+        assert_eq!(verdict(Some("42"), Some("43")), Verdict::Fail);
+        assert_eq!(verdict(Some("42"), None), Verdict::Fail);
+        assert_eq!(verdict(None, Some("42")), Verdict::Missing);
+        assert_eq!(verdict(None, None), Verdict::Missing);
+    }
+
+    #[test]
+    fn round_trips_answers() {
+        let answers = Answers {
+            part_1: Some("1234".into()),
+            part_2: Some("#..#\n####\n#..#".into()),
+        };
+
+        assert_eq!(Answers::parse(&answers.to_file_string()), answers);
+    }
+
+    #[test]
+    fn handles_missing_parts() {
+        let answers = Answers::parse("part 2:\n42\n");
+        assert_eq!(answers.part_1, None);
+        assert_eq!(answers.get(2), Some("42"));
+
+        assert_eq!(Answers::parse(""), Answers::default());
+        assert_eq!(Answers::parse("part 1:\n\npart 2:\n7").part_1, None);
+    }
+
+    #[test]
+    fn trims_trailing_newlines() {
+        let mut answers = Answers::default();
+        answers.set(1, "42\n\n");
+        assert_eq!(answers.get(1), Some("42"));
+    }
+}
@@ -0,0 +1,520 @@
+/// Client for the puzzle site: reads puzzle descriptions, downloads inputs and submits answers.
+///
+/// Requests are authenticated with the session cookie of a logged-in browser, read from the
+/// `AOC_SESSION` environment variable or from `~/.adventofcode.session` (the file aoc-cli uses).
+/// `AOC_BASE_URL` points the client at a different server, e.g. a local stand-in.
+use std::fmt::Display;
+use std::time::Duration;
+use std::{env, fs};
+
+use crate::template::Puzzle;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+const SESSION_FILE_NAME: &str = ".adventofcode.session";
+const USER_AGENT: &str = concat!("advent_of_code/", env!("CARGO_PKG_VERSION"));
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug)]
+pub enum ClientError {
+    MissingSession,
+    /// The server answered with an error status, e.g. `404` for puzzles that are not unlocked yet.
+    Status(u16),
+    Transport(String),
+    UnexpectedResponse,
+}
+
+impl Display for ClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientError::MissingSession => write!(
+                f,
+                "no session cookie found. Set AOC_SESSION or write it to ~/{SESSION_FILE_NAME}."
+            ),
+            ClientError::Status(status) => write!(f, "server responded with status {status}."),
+            ClientError::Transport(e) => write!(f, "request failed: {e}."),
+            ClientError::UnexpectedResponse => write!(f, "could not understand server response."),
+        }
+    }
+}
+
+impl From<ureq::Error> for ClientError {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            ureq::Error::Status(status, _) => ClientError::Status(status),
+            ureq::Error::Transport(e) => ClientError::Transport(e.to_string()),
+        }
+    }
+}
+
+impl From<std::io::Error> for ClientError {
+    fn from(e: std::io::Error) -> Self {
+        ClientError::Transport(e.to_string())
+    }
+}
+
+/// The server's verdict on a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SubmitOutcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint in which direction.
+    Incorrect,
+    /// An answer was submitted too recently, the answer was not checked.
+    Wait(Duration),
+    /// The part was solved before (or is not unlocked yet), the answer was not checked.
+    AlreadySolved,
+}
+
+impl SubmitOutcome {
+    /// Reads the outcome from the page the server responds with.
+    fn parse(html: &str) -> Option<Self> {
+        let text = strip_tags(articles(html).first()?);
+
+        if text.contains("That's the right answer") {
+            Some(SubmitOutcome::Correct)
+        } else if text.contains("your answer is too high") {
+            Some(SubmitOutcome::TooHigh)
+        } else if text.contains("your answer is too low") {
+            Some(SubmitOutcome::TooLow)
+        } else if text.contains("That's not the right answer") {
+            Some(SubmitOutcome::Incorrect)
+        } else if text.contains("You gave an answer too recently") {
+            Some(SubmitOutcome::Wait(parse_wait(&text).unwrap_or_default()))
+        } else if text.contains("You don't seem to be solving the right level") {
+            Some(SubmitOutcome::AlreadySolved)
+        } else {
+            None
+        }
+    }
+}
+
+impl Display for SubmitOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmitOutcome::Correct => write!(f, "That's the right answer!"),
+            SubmitOutcome::TooHigh => write!(f, "That's not the right answer, it is too high."),
+            SubmitOutcome::TooLow => write!(f, "That's not the right answer, it is too low."),
+            SubmitOutcome::Incorrect => write!(f, "That's not the right answer."),
+            SubmitOutcome::Wait(wait) => write!(
+                f,
+                "You gave an answer too recently, wait {}m {}s before trying again.",
+                wait.as_secs() / 60,
+                wait.as_secs() % 60
+            ),
+            SubmitOutcome::AlreadySolved => {
+                write!(f, "This part is already solved or not unlocked yet.")
+            }
+        }
+    }
+}
+
+/// Where and as whom the client connects.
+#[derive(Clone, Debug)]
+pub struct Config {
+    pub base_url: String,
+    pub session: String,
+}
+
+impl Config {
+    /// Reads the configuration from `AOC_BASE_URL` and the session cookie, see the module docs.
+    pub fn from_env() -> Result<Self, ClientError> {
+        let base_url = base_url_from_env();
+
+        let session = env::var("AOC_SESSION")
+            .ok()
+            .or_else(|| {
+                let home = env::var("HOME").or_else(|_| env::var("USERPROFILE")).ok()?;
+                fs::read_to_string(format!("{home}/{SESSION_FILE_NAME}")).ok()
+            })
+            .map(|session| session.trim().to_string())
+            .filter(|session| !session.is_empty())
+            .ok_or(ClientError::MissingSession)?;
+
+        Ok(Config { base_url, session })
+    }
+}
+
+/// The server set with `AOC_BASE_URL`, or the puzzle site itself.
+pub fn base_url_from_env() -> String {
+    env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into())
+}
+
+/// URL of a page of `puzzle` on the server at `base_url`. An empty path is the puzzle description.
+pub fn url(base_url: &str, puzzle: Puzzle, path: &str) -> String {
+    format!(
+        "{}/{}/day/{}{path}",
+        base_url.trim_end_matches('/'),
+        puzzle.year,
+        puzzle.day.into_inner()
+    )
+}
+
+pub struct Client {
+    config: Config,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(config: Config) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(USER_AGENT)
+            .timeout(TIMEOUT)
+            .build();
+
+        Client { config, agent }
+    }
+
+    pub fn from_env() -> Result<Self, ClientError> {
+        Config::from_env().map(Client::new)
+    }
+
+    /// The puzzle description as markdown. Contains part two once part one is solved.
+    pub fn puzzle(&self, puzzle: Puzzle) -> Result<String, ClientError> {
+        let html = self.get(&self.url(puzzle, ""))?;
+        let articles = articles(&html);
+
+        if articles.is_empty() {
+            return Err(ClientError::UnexpectedResponse);
+        }
+
+        let markdown: Vec<String> = articles.iter().map(|a| to_markdown(a)).collect();
+        Ok(markdown.join("\n\n"))
+    }
+
+    pub fn input(&self, puzzle: Puzzle) -> Result<String, ClientError> {
+        self.get(&self.url(puzzle, "/input"))
+    }
+
+    pub fn submit(
+        &self,
+        puzzle: Puzzle,
+        part: u8,
+        answer: &str,
+    ) -> Result<SubmitOutcome, ClientError> {
+        let html = self
+            .agent
+            .post(&self.url(puzzle, "/answer"))
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?
+            .into_string()?;
+
+        SubmitOutcome::parse(&html).ok_or(ClientError::UnexpectedResponse)
+    }
+
+    fn get(&self, url: &str) -> Result<String, ClientError> {
+        Ok(self
+            .agent
+            .get(url)
+            .set("Cookie", &self.cookie())
+            .call()?
+            .into_string()?)
+    }
+
+    /// URL of a page of `puzzle`, e.g. `/input`. An empty path is the puzzle description.
+    pub fn url(&self, puzzle: Puzzle, path: &str) -> String {
+        url(&self.config.base_url, puzzle, path)
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.config.session)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The inner HTML of all `<article>` elements.
+fn articles(html: &str) -> Vec<&str> {
+    let mut articles = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<article") {
+        let Some(open_end) = rest[start..].find('>') else {
+            break;
+        };
+        let content_start = start + open_end + 1;
+        let Some(len) = rest[content_start..].find("</article>") else {
+            break;
+        };
+
+        articles.push(&rest[content_start..content_start + len]);
+        rest = &rest[content_start + len..];
+    }
+
+    articles
+}
+
+/// Splits HTML into text and tags. Tags are yielded with their name and whether they close.
+fn tokens(html: &str) -> Vec<Token<'_>> {
+    let mut tokens = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        if start > 0 {
+            tokens.push(Token::Text(&rest[..start]));
+        }
+
+        let Some(len) = rest[start..].find('>') else {
+            break;
+        };
+
+        let tag = &rest[start + 1..start + len];
+        let is_closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default();
+
+        tokens.push(Token::Tag(name, is_closing));
+        rest = &rest[start + len + 1..];
+    }
+
+    if !rest.is_empty() {
+        tokens.push(Token::Text(rest));
+    }
+
+    tokens
+}
+
+enum Token<'a> {
+    Text(&'a str),
+    /// Name of the tag and whether it is a closing tag.
+    Tag(&'a str, bool),
+}
+
+fn strip_tags(html: &str) -> String {
+    tokens(html)
+        .into_iter()
+        .filter_map(|token| match token {
+            Token::Text(text) => Some(decode_entities(text)),
+            Token::Tag(..) => None,
+        })
+        .collect()
+}
+
+/// Converts the HTML of a puzzle description to markdown.
+fn to_markdown(html: &str) -> String {
+    let mut out = String::new();
+    let mut in_pre = false;
+
+    for token in tokens(html) {
+        match token {
+            Token::Text(text) => out.push_str(&decode_entities(text)),
+            Token::Tag(name, is_closing) => match (name, is_closing) {
+                ("h2", false) => out.push_str("## "),
+                ("h2" | "p" | "ul", true) => out.push_str("\n\n"),
+                ("pre", false) => {
+                    in_pre = true;
+                    out.push_str("```\n");
+                }
+                ("pre", true) => {
+                    in_pre = false;
+                    out.push_str("```\n\n");
+                }
+                ("code", _) if !in_pre => out.push('`'),
+                // code blocks are verbatim, emphasis inside them would become literal asterisks.
+                ("em", _) if !in_pre => out.push('*'),
+                ("li", false) => out.push_str("- "),
+                ("li", true) | ("br", _) => out.push('\n'),
+                _ => {}
+            },
+        }
+    }
+
+    out.trim().to_string()
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Reads a wait time like `You have 4m 37s left to wait.`
+fn parse_wait(text: &str) -> Option<Duration> {
+    let start = text.find("You have ")? + "You have ".len();
+    let len = text[start..].find(" left to wait")?;
+
+    let mut secs = 0;
+
+    for part in text[start..start + len].split_whitespace() {
+        let (value, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+        let value: u64 = value.parse().ok()?;
+
+        secs += match unit {
+            "h" => value * 3600,
+            "m" => value * 60,
+            "s" => value,
+            _ => return None,
+        };
+    }
+
+    Some(Duration::from_secs(secs))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread::{self, JoinHandle};
+    use std::time::Duration;
+
+    use super::{to_markdown, Client, ClientError, Config, SubmitOutcome};
+    use crate::day;
+    use crate::template::{Puzzle, Year};
+
+    const PUZZLE: Puzzle = Puzzle::new(Year::primary(), day!(1));
+
+    /// A local stand-in for the puzzle site. Answers one request per response, in order, and
+    /// returns the requests it received (request line, headers and body).
+    fn serve(responses: Vec<(u16, &'static str)>) -> (Client, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let server = thread::spawn(move || {
+            let mut requests = vec![];
+
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut request = String::new();
+                let mut content_length = 0;
+
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+
+                    if let Some(len) = line.to_lowercase().strip_prefix("content-length:") {
+                        content_length = len.trim().parse().unwrap();
+                    }
+
+                    if line == "\r\n" {
+                        break;
+                    }
+                    request.push_str(&line);
+                }
+
+                let mut content = vec![0; content_length];
+                reader.read_exact(&mut content).unwrap();
+                request.push_str(&String::from_utf8(content).unwrap());
+                requests.push(request);
+
+                let response = format!(
+                    "HTTP/1.1 {status} Stand-in\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                reader.get_mut().write_all(response.as_bytes()).unwrap();
+            }
+
+            requests
+        });
+
+        let client = Client::new(Config {
+            base_url,
+            session: "abc123".into(),
+        });
+
+        (client, server)
+    }
+
+    #[test]
+    fn downloads_inputs() {
+        let (client, server) = serve(vec![(200, "1\n2\n3\n")]);
+
+        assert_eq!(client.input(PUZZLE).unwrap(), "1\n2\n3\n");
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with(&format!("GET /{}/day/1/input ", Year::primary())));
+        assert!(requests[0].contains("session=abc123"));
+    }
+
+    #[test]
+    fn reads_puzzles() {
+        let page = r#"<main><article class="day-desc"><h2>--- Day 1: Test ---</h2><p>Find <em>the</em> sum of <code>a &lt; b</code>:</p><pre><code>1 &amp; 2
+</code></pre></article><p>Your puzzle answer was <code>3</code>.</p></main>"#;
+        let (client, server) = serve(vec![(200, page)]);
+
+        assert_eq!(
+            client.puzzle(PUZZLE).unwrap(),
+            "## --- Day 1: Test ---\n\nFind *the* sum of `a < b`:\n\n```\n1 & 2\n```"
+        );
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (client, server) = serve(vec![
+            (200, "<main><article><p>That's the right answer! You are <em>one gold star</em> closer.</p></article></main>"),
+            (200, "<main><article><p>That's not the right answer; your answer is too high. Please wait one minute.</p></article></main>"),
+            (200, "<main><article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 4m 37s left to wait. <a href=\"/2019/day/1\">[Return to Day 1]</a></p></article></main>"),
+            (200, "<main><article><p>You don't seem to be solving the right level.  Did you already complete it?</p></article></main>"),
+        ]);
+
+        assert_eq!(
+            client.submit(PUZZLE, 1, "42").unwrap(),
+            SubmitOutcome::Correct
+        );
+        assert_eq!(
+            client.submit(PUZZLE, 2, "42").unwrap(),
+            SubmitOutcome::TooHigh
+        );
+        assert_eq!(
+            client.submit(PUZZLE, 2, "41").unwrap(),
+            SubmitOutcome::Wait(Duration::from_secs(277))
+        );
+        assert_eq!(
+            client.submit(PUZZLE, 1, "42").unwrap(),
+            SubmitOutcome::AlreadySolved
+        );
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with(&format!("POST /{}/day/1/answer ", Year::primary())));
+        assert!(requests[0].ends_with("level=1&answer=42"));
+        assert!(requests[1].ends_with("level=2&answer=42"));
+    }
+
+    #[test]
+    fn reports_errors() {
+        let (client, server) = serve(vec![
+            (404, "Not Found"),
+            (200, "<main><p>Something else</p></main>"),
+        ]);
+
+        assert!(matches!(
+            client.input(PUZZLE),
+            Err(ClientError::Status(404))
+        ));
+        assert!(matches!(
+            client.submit(PUZZLE, 1, "1"),
+            Err(ClientError::UnexpectedResponse)
+        ));
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn converts_lists() {
+        assert_eq!(
+            to_markdown("<p>Steps:</p><ul><li>one</li><li><code>two</code></li></ul>"),
+            "Steps:\n\n- one\n- `two`"
+        );
+    }
+
+    #[test]
+    fn keeps_code_blocks_verbatim() {
+        assert_eq!(
+            to_markdown(
+                "<pre><code>1 <em>2</em>\n</code></pre><p>Sum: <code><em>3</em></code></p>"
+            ),
+            "```\n1 2\n```\n\nSum: `*3*`"
+        );
+    }
+}
+
+/* -------------------------------------------------------------------------- */
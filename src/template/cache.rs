@@ -0,0 +1,253 @@
+/// Metadata of downloaded inputs and puzzles, stored in `data/cache.json` per year.
+///
+/// Every downloaded file is recorded with the time it was fetched, the URL it was fetched from and
+/// a hash of its contents. `cargo download` skips files that are still intact, and [`read_file`]
+/// refuses to read a downloaded file that changed since, so solutions never run on a truncated or
+/// accidentally edited input. `cargo download --force` fetches the files again.
+///
+/// [`read_file`]: crate::template::read_file
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::{fs, io};
+
+use tinyjson::JsonValue;
+
+use crate::template::Year;
+
+#[must_use]
+pub fn get_path_for_cache(year: Year) -> String {
+    format!("{}/cache.json", year.data_dir())
+}
+
+/// Metadata of a single downloaded file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Entry {
+    /// Seconds since the unix epoch.
+    pub fetched_at: u64,
+    pub hash: String,
+    pub source: String,
+}
+
+/// Metadata of all downloaded files of a year, keyed by their path relative to `dir`.
+#[derive(Clone, Debug, Default)]
+pub struct Cache {
+    pub entries: HashMap<String, Entry>,
+    /// The directory the cache was read from, see [`Cache::read`].
+    dir: PathBuf,
+}
+
+impl Cache {
+    /// Reads the cache metadata of `year` from `dir`. Returns an empty cache if nothing was
+    /// downloaded yet.
+    pub fn read(dir: &Path, year: Year) -> Result<Self, String> {
+        let path = dir.join(get_path_for_cache(year));
+
+        match fs::read_to_string(&path) {
+            Ok(s) => JsonValue::from_str(&s)
+                .map_err(|_| "not valid JSON.".to_string())
+                .and_then(|json| Cache::try_from(&json))
+                .map(|cache| Cache {
+                    dir: dir.to_path_buf(),
+                    ..cache
+                })
+                .map_err(|e| format!("{}: {e}", path.display())),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Cache {
+                dir: dir.to_path_buf(),
+                ..Cache::default()
+            }),
+            Err(e) => Err(format!("{}: {e}", path.display())),
+        }
+    }
+
+    pub fn store(&self, year: Year) -> io::Result<()> {
+        let json = JsonValue::from(self)
+            .format()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+
+        fs::write(self.dir.join(get_path_for_cache(year)), json)
+    }
+
+    /// Returns the entry of `path` if the file exists and is unchanged since it was downloaded.
+    pub fn get_intact(&self, path: &str) -> Option<&Entry> {
+        let entry = self.entries.get(path)?;
+        let contents = fs::read(self.dir.join(path)).ok()?;
+        (entry.hash == hash(&contents)).then_some(entry)
+    }
+
+    pub fn record(&mut self, path: &str, contents: &[u8], source: &str, fetched_at: u64) {
+        self.entries.insert(
+            path.into(),
+            Entry {
+                fetched_at,
+                hash: hash(contents),
+                source: source.into(),
+            },
+        );
+    }
+
+    /// Checks that `contents` of a file at `path` match the recorded hash.
+    /// Files that were not downloaded are not checked.
+    pub fn verify(&self, path: &str, contents: &[u8]) -> Result<(), String> {
+        match self.entries.get(path) {
+            Some(entry) if entry.hash != hash(contents) => Err(format!(
+                "\"{path}\" changed since it was downloaded from {}.",
+                entry.source
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// FNV-1a hash of `contents`, prefixed with the name of the algorithm.
+#[must_use]
+pub fn hash(contents: &[u8]) -> String {
+    let hash = contents
+        .iter()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+
+    format!("fnv1a64:{hash:016x}")
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Cache> for JsonValue {
+    fn from(value: &Cache) -> Self {
+        let entries = value
+            .entries
+            .iter()
+            .map(|(path, entry)| {
+                let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+                #[allow(clippy::cast_precision_loss)]
+                map.insert(
+                    "fetched_at".into(),
+                    JsonValue::Number(entry.fetched_at as f64),
+                );
+                map.insert("hash".into(), JsonValue::String(entry.hash.clone()));
+                map.insert("source".into(), JsonValue::String(entry.source.clone()));
+
+                (path.clone(), JsonValue::Object(map))
+            })
+            .collect();
+
+        JsonValue::Object(entries)
+    }
+}
+
+impl TryFrom<&JsonValue> for Cache {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected cache to be a JSON object.")?;
+
+        let entries = json
+            .iter()
+            .map(|(path, entry)| {
+                let entry = entry
+                    .get::<HashMap<String, JsonValue>>()
+                    .ok_or(format!("expected entry of \"{path}\" to be a JSON object."))?;
+
+                let string = |key: &str| {
+                    entry
+                        .get(key)
+                        .and_then(|v| v.get::<String>())
+                        .cloned()
+                        .ok_or(format!("expected {key} of \"{path}\" to be a string."))
+                };
+
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let fetched_at = entry
+                    .get("fetched_at")
+                    .and_then(|v| v.get::<f64>())
+                    .map(|fetched_at| *fetched_at as u64)
+                    .ok_or(format!("expected fetched_at of \"{path}\" to be a number."))?;
+
+                Ok((
+                    path.clone(),
+                    Entry {
+                        fetched_at,
+                        hash: string("hash")?,
+                        source: string("source")?,
+                    },
+                ))
+            })
+            .collect::<Result<_, String>>()?;
+
+        Ok(Cache {
+            entries,
+            ..Cache::default()
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::str::FromStr;
+    use std::{env, fs, process};
+
+    use tinyjson::JsonValue;
+
+    use super::{hash, Cache};
+    use crate::template::Year;
+
+    #[test]
+    fn hashes_contents() {
+        assert_eq!(hash(b""), "fnv1a64:cbf29ce484222325");
+        assert_eq!(hash(b"a"), "fnv1a64:af63dc4c8601ec8c");
+        assert_ne!(hash(b"1\n2\n"), hash(b"1\n2"));
+    }
+
+    #[test]
+    fn verifies_downloaded_files() {
+        let mut cache = Cache::default();
+        cache.record("data/inputs/01.txt", b"1\n2\n", "https://example.com", 1);
+
+        assert!(cache.verify("data/inputs/01.txt", b"1\n2\n").is_ok());
+        assert!(cache.verify("data/inputs/01.txt", b"").is_err());
+        assert!(cache.verify("data/examples/01.txt", b"").is_ok());
+    }
+
+    #[test]
+    fn resolves_paths_against_the_cache_dir() {
+        let dir = env::temp_dir().join(format!("aoc-cache-{}", process::id()));
+        let year = Year::primary();
+        let path = "data/inputs/01.txt";
+
+        fs::create_dir_all(dir.join("data/inputs")).unwrap();
+        fs::write(dir.join(path), "1\n").unwrap();
+
+        let mut cache = Cache::read(&dir, year).unwrap();
+        cache.record(path, b"1\n", "https://example.com", 1);
+        cache.store(year).unwrap();
+
+        let cache = Cache::read(&dir, year).unwrap();
+        assert!(cache.get_intact(path).is_some());
+
+        fs::write(dir.join(path), "2\n").unwrap();
+        assert!(cache.get_intact(path).is_none());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn round_trips_json() {
+        let mut cache = Cache::default();
+        cache.record("data/inputs/01.txt", b"1", "https://example.com/input", 42);
+        cache.record("data/puzzles/01.md", b"# 1", "https://example.com", 43);
+
+        let json = JsonValue::from(&cache).stringify().unwrap();
+        let parsed = Cache::try_from(&JsonValue::from_str(&json).unwrap()).unwrap();
+
+        assert_eq!(parsed.entries, cache.entries);
+        assert_eq!(parsed.entries["data/inputs/01.txt"].fetched_at, 42);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
@@ -0,0 +1,399 @@
+/// Benchmark charts rendered from timings.
+///
+/// `cargo time --store` writes a self-contained SVG bar chart of all parts (log-scaled, so fast
+/// and slow days fit into one chart) to `data/benchmarks.svg` of the benchmarked year. With
+/// `--html`, it additionally writes `data/benchmarks.html`, a report with sortable tables and sparklines of the benchmark
+/// history. Both are linked from the benchmark table of the README.
+use std::fmt::Write;
+use std::time::Duration;
+use std::{fs, io};
+
+use crate::template::history::{self, Entry};
+use crate::template::timings::Timings;
+use crate::template::Year;
+
+const COLORS: [&str; 2] = ["#4e79a7", "#f28e2b"];
+const BAR_WIDTH: f64 = 12.0;
+const GROUP_WIDTH: f64 = 2.0 * BAR_WIDTH + 12.0;
+const PLOT_HEIGHT: f64 = 200.0;
+const MARGIN_LEFT: f64 = 64.0;
+const MARGIN_RIGHT: f64 = 16.0;
+const MARGIN_TOP: f64 = 40.0;
+const MARGIN_BOTTOM: f64 = 32.0;
+const MIN_WIDTH: f64 = 240.0;
+
+const SPARKLINE_WIDTH: f64 = 120.0;
+const SPARKLINE_HEIGHT: f64 = 24.0;
+
+/// Maps durations to the y coordinate of the chart, one grid line per power of ten.
+struct LogScale {
+    min_exponent: i32,
+    max_exponent: i32,
+}
+
+impl LogScale {
+    fn new(values: &[f64]) -> Self {
+        let min = values
+            .iter()
+            .copied()
+            .fold(f64::INFINITY, f64::min)
+            .max(1.0);
+        let max = values
+            .iter()
+            .copied()
+            .fold(f64::NEG_INFINITY, f64::max)
+            .max(1.0);
+
+        #[allow(clippy::cast_possible_truncation)]
+        let min_exponent = min.log10().floor() as i32;
+        #[allow(clippy::cast_possible_truncation)]
+        let max_exponent = (max.log10().ceil() as i32).max(min_exponent + 1);
+
+        LogScale {
+            min_exponent,
+            max_exponent,
+        }
+    }
+
+    fn y(&self, nanos: f64) -> f64 {
+        let range = f64::from(self.max_exponent - self.min_exponent);
+        let position = (nanos.max(1.0).log10() - f64::from(self.min_exponent)) / range;
+        MARGIN_TOP + PLOT_HEIGHT * (1.0 - position)
+    }
+}
+
+/// Renders a bar chart of the mean duration of every part.
+#[must_use]
+pub fn render_svg(timings: &Timings) -> String {
+    let values: Vec<f64> = timings
+        .data
+        .iter()
+        .flat_map(|t| [t.part_nanos(1), t.part_nanos(2)])
+        .flatten()
+        .collect();
+
+    // wide enough for the legend, even with few days.
+    #[allow(clippy::cast_precision_loss)]
+    let width =
+        (MARGIN_LEFT + MARGIN_RIGHT + GROUP_WIDTH * timings.data.len() as f64).max(MIN_WIDTH);
+    let height = MARGIN_TOP + PLOT_HEIGHT + MARGIN_BOTTOM;
+    let bottom = MARGIN_TOP + PLOT_HEIGHT;
+
+    let mut svg = String::new();
+
+    writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" font-family="sans-serif" font-size="11">"#
+    )
+    .unwrap();
+    writeln!(
+        svg,
+        r##"<rect width="{width}" height="{height}" fill="#ffffff"/>"##
+    )
+    .unwrap();
+
+    if values.is_empty() {
+        writeln!(
+            svg,
+            r#"<text x="{}" y="{}" text-anchor="middle">No benchmarks yet.</text>"#,
+            width / 2.0,
+            height / 2.0
+        )
+        .unwrap();
+        svg.push_str("</svg>\n");
+        return svg;
+    }
+
+    let scale = LogScale::new(&values);
+
+    // legend.
+    for (i, color) in COLORS.iter().enumerate() {
+        #[allow(clippy::cast_precision_loss)]
+        let x = MARGIN_LEFT + i as f64 * 64.0;
+        writeln!(
+            svg,
+            r#"<rect x="{x}" y="12" width="10" height="10" fill="{color}"/><text x="{}" y="21">Part {}</text>"#,
+            x + 14.0,
+            i + 1
+        )
+        .unwrap();
+    }
+
+    // grid lines, one per power of ten.
+    for exponent in scale.min_exponent..=scale.max_exponent {
+        let nanos = 10_f64.powi(exponent);
+        let y = scale.y(nanos);
+        writeln!(
+            svg,
+            r##"<line x1="{MARGIN_LEFT}" y1="{y}" x2="{}" y2="{y}" stroke="#dddddd"/><text x="{}" y="{}" text-anchor="end">{}</text>"##,
+            width - MARGIN_RIGHT,
+            MARGIN_LEFT - 6.0,
+            y + 4.0,
+            format_tick(nanos)
+        )
+        .unwrap();
+    }
+
+    for (i, timing) in timings.data.iter().enumerate() {
+        #[allow(clippy::cast_precision_loss)]
+        let group_x = MARGIN_LEFT + GROUP_WIDTH * i as f64 + 6.0;
+
+        for part in [1, 2] {
+            let Some(nanos) = timing.part_nanos(part) else {
+                continue;
+            };
+
+            let x = group_x + BAR_WIDTH * f64::from(part - 1);
+            let y = scale.y(nanos);
+
+            writeln!(
+                svg,
+                r#"<rect x="{x}" y="{y:.2}" width="{BAR_WIDTH}" height="{:.2}" fill="{}"><title>Day {} / Part {part}: {}</title></rect>"#,
+                bottom - y,
+                COLORS[usize::from(part - 1)],
+                timing.day,
+                format_nanos(nanos)
+            )
+            .unwrap();
+        }
+
+        writeln!(
+            svg,
+            r#"<text x="{}" y="{}" text-anchor="middle">{}</text>"#,
+            group_x + BAR_WIDTH,
+            bottom + 16.0,
+            timing.day.into_inner()
+        )
+        .unwrap();
+    }
+
+    writeln!(
+        svg,
+        r##"<line x1="{MARGIN_LEFT}" y1="{bottom}" x2="{}" y2="{bottom}" stroke="#333333"/>"##,
+        width - MARGIN_RIGHT
+    )
+    .unwrap();
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Renders a sparkline of the total time of a day across benchmark runs.
+fn render_sparkline(values: &[f64]) -> String {
+    if values.len() < 2 {
+        return "-".into();
+    }
+
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+    #[allow(clippy::cast_precision_loss)]
+    let step = SPARKLINE_WIDTH / (values.len() - 1) as f64;
+
+    let points: Vec<String> = values
+        .iter()
+        .enumerate()
+        .map(|(i, value)| {
+            let position = if max > min {
+                (value - min) / (max - min)
+            } else {
+                0.5
+            };
+
+            #[allow(clippy::cast_precision_loss)]
+            let x = step * i as f64;
+            let y = 2.0 + (SPARKLINE_HEIGHT - 4.0) * (1.0 - position);
+            format!("{x:.1},{y:.1}")
+        })
+        .collect();
+
+    format!(
+        r##"<svg width="{SPARKLINE_WIDTH}" height="{SPARKLINE_HEIGHT}"><polyline points="{}" fill="none" stroke="#4e79a7" stroke-width="1.5"/></svg>"##,
+        points.join(" ")
+    )
+}
+
+/// Renders an HTML report with the chart, a sortable table of all parts and the history of each day.
+#[must_use]
+pub fn render_html(timings: &Timings, history: &[Entry]) -> String {
+    let mut rows = String::new();
+
+    for timing in &timings.data {
+        let cell = |nanos: Option<f64>| match nanos {
+            Some(nanos) => format!(r#"<td data-value="{nanos}">{}</td>"#, format_nanos(nanos)),
+            None => r#"<td data-value="">-</td>"#.to_string(),
+        };
+
+        writeln!(
+            rows,
+            r#"<tr><td data-value="{}">{}</td>{}{}{}<td>{}</td></tr>"#,
+            timing.day.into_inner(),
+            timing.day,
+            cell(timing.part_nanos(1)),
+            cell(timing.part_nanos(2)),
+            cell(Some(timing.total_nanos)),
+            render_sparkline(&history::totals(history, timing.day))
+        )
+        .unwrap();
+    }
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>Benchmarks</title>
+<style>
+body {{ font-family: sans-serif; margin: 2rem; }}
+table {{ border-collapse: collapse; }}
+th, td {{ padding: 0.25rem 0.75rem; border-bottom: 1px solid #dddddd; text-align: right; }}
+th {{ cursor: pointer; user-select: none; }}
+th[data-order="asc"]::after {{ content: " ▲"; }}
+th[data-order="desc"]::after {{ content: " ▼"; }}
+</style>
+</head>
+<body>
+<h1>Benchmarks</h1>
+{svg}
+<p>Total: {total:.2}ms over {runs} recorded runs. Click a column to sort.</p>
+<table>
+<thead><tr><th>Day</th><th>Part 1</th><th>Part 2</th><th>Total</th><th>History</th></tr></thead>
+<tbody>
+{rows}</tbody>
+</table>
+<script>
+document.querySelectorAll("th").forEach((th, column) => {{
+  th.addEventListener("click", () => {{
+    const body = th.closest("table").tBodies[0];
+    const order = th.dataset.order === "asc" ? "desc" : "asc";
+    document.querySelectorAll("th").forEach((other) => delete other.dataset.order);
+    th.dataset.order = order;
+    const value = (row) => {{
+      const x = parseFloat(row.cells[column].dataset.value);
+      return isNaN(x) ? Infinity : x;
+    }};
+    [...body.rows]
+      .sort((a, b) => (value(a) - value(b) || 0) * (order === "asc" ? 1 : -1))
+      .forEach((row) => body.appendChild(row));
+  }});
+}});
+</script>
+</body>
+</html>
+"#,
+        svg = render_svg(timings),
+        total = timings.total_millis(),
+        runs = history.len(),
+    )
+}
+
+#[must_use]
+pub fn get_path_for_svg(year: Year) -> String {
+    format!("./{}/benchmarks.svg", year.data_dir())
+}
+
+#[must_use]
+pub fn get_path_for_html(year: Year) -> String {
+    format!("./{}/benchmarks.html", year.data_dir())
+}
+
+/// Writes the SVG chart of `year` to [`get_path_for_svg`].
+pub fn store_svg(year: Year, timings: &Timings) -> io::Result<()> {
+    fs::write(get_path_for_svg(year), render_svg(timings))
+}
+
+/// Writes the HTML report of `year` to [`get_path_for_html`].
+pub fn store_html(year: Year, timings: &Timings, history: &[Entry]) -> io::Result<()> {
+    fs::write(get_path_for_html(year), render_html(timings, history))
+}
+
+/// Formats a power of ten without decimals, e.g. `100µs`.
+fn format_tick(nanos: f64) -> String {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let duration = Duration::from_nanos(nanos.round() as u64);
+    format!("{duration:?}")
+}
+
+fn format_nanos(nanos: f64) -> String {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let duration = Duration::from_nanos(nanos.round() as u64);
+    format!("{duration:.1?}")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{render_html, render_sparkline, render_svg, LogScale, MARGIN_TOP, PLOT_HEIGHT};
+    use crate::day;
+    use crate::template::timings::{Timing, Timings};
+
+    fn timing(day: crate::template::Day, part_1: Option<&str>, part_2: Option<&str>) -> Timing {
+        Timing {
+            day,
+            part_1: part_1.map(Into::into),
+            part_2: part_2.map(Into::into),
+            parse: None,
+            total_nanos: 0.0,
+            parse_stats: None,
+            part_1_stats: None,
+            part_2_stats: None,
+            part_1_memory: None,
+            part_2_memory: None,
+        }
+    }
+
+    fn get_mock_timings() -> Timings {
+        Timings {
+            data: vec![
+                timing(day!(1), Some("1.5µs"), Some("20.0ms")),
+                timing(day!(3), Some("800.0ns"), None),
+            ],
+        }
+    }
+
+    #[test]
+    fn scales_logarithmically() {
+        let scale = LogScale::new(&[800.0, 2e7]);
+        assert_eq!((scale.min_exponent, scale.max_exponent), (2, 8));
+        assert_eq!(scale.y(1e8), MARGIN_TOP);
+        assert_eq!(scale.y(1e2), MARGIN_TOP + PLOT_HEIGHT);
+        assert_eq!(scale.y(1e5), MARGIN_TOP + PLOT_HEIGHT / 2.0);
+
+        let scale = LogScale::new(&[1000.0]);
+        assert_eq!((scale.min_exponent, scale.max_exponent), (3, 4));
+    }
+
+    #[test]
+    fn renders_bars_per_part() {
+        let svg = render_svg(&get_mock_timings());
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("<title>").count(), 3);
+        assert!(svg.contains("<title>Day 01 / Part 2: 20.0ms</title>"));
+        assert!(svg.contains(">100ns</text>"));
+        assert!(svg.contains(">100ms</text>"));
+    }
+
+    #[test]
+    fn renders_empty_timings() {
+        let svg = render_svg(&Timings::default());
+        assert!(svg.contains("No benchmarks yet."));
+    }
+
+    #[test]
+    fn renders_sparklines() {
+        assert_eq!(render_sparkline(&[1.0]), "-");
+        let sparkline = render_sparkline(&[2.0, 1.0, 3.0]);
+        assert!(sparkline.contains(r#"points="0.0,12.0 60.0,22.0 120.0,2.0""#));
+    }
+
+    #[test]
+    fn renders_html_report() {
+        let html = render_html(&get_mock_timings(), &[]);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert_eq!(html.matches("<tr><td").count(), 2);
+        assert!(html.contains(r#"<td data-value="800">800.0ns</td>"#));
+        assert!(html.contains(r#"<td data-value="">-</td>"#));
+    }
+}
@@ -1,5 +1,17 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::time::Duration;
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::run_multi::{run_multi, RunOptions};
+use crate::template::{all_days, Year};
+
+pub fn handle(year: Year, is_release: bool, spawn: bool, jobs: usize, timeout: Option<Duration>) {
+    let options = RunOptions {
+        is_release,
+        is_timed: false,
+        spawn,
+        jobs,
+        timeout,
+        memory: false,
+    };
+
+    run_multi(year, &all_days().collect(), options);
 }
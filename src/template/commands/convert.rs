@@ -0,0 +1,45 @@
+use std::{fs, process};
+
+use crate::template::Puzzle;
+use crate::{decode_program, encode_program, parse_intcode_program};
+
+pub fn handle(puzzle: Puzzle) {
+    let input_path = puzzle.data_path("inputs", "txt");
+    let image_path = puzzle.data_path("inputs", "icb");
+
+    let input = match fs::read_to_string(&input_path) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("Failed to read input file \"{input_path}\": {e}");
+            process::exit(1);
+        }
+    };
+
+    let program = match parse_intcode_program(&input) {
+        Ok(program) => program,
+        Err(e) => {
+            eprintln!("Failed to parse Intcode program \"{input_path}\": {e}");
+            process::exit(1);
+        }
+    };
+
+    let image = encode_program(&program);
+
+    // guard against writing an image that does not load back to the same program.
+    if decode_program(&image).ok().as_ref() != Some(&program) {
+        eprintln!("Failed to verify binary image for \"{input_path}\".");
+        process::exit(1);
+    }
+
+    if let Err(e) = fs::write(&image_path, &image) {
+        eprintln!("Failed to write binary image: {e}");
+        process::exit(1);
+    }
+
+    println!(
+        "🎄 Converted {} words to \"{image_path}\" ({} -> {} bytes).",
+        program.len(),
+        input.len(),
+        image.len()
+    );
+}
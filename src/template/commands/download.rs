@@ -1,14 +1,85 @@
-use crate::template::{aoc_cli, Day};
-use std::process;
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::{fs, io, process};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+use crate::template::aoc_client::{Client, ClientError};
+use crate::template::cache::Cache;
+use crate::template::history::format_date;
+use crate::template::Puzzle;
+
+pub fn handle(puzzle: Puzzle, force: bool) {
+    if let Err(e) = download(puzzle, force) {
+        eprintln!("failed to download puzzle: {e}");
         process::exit(1);
     }
+}
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
+fn download(puzzle: Puzzle, force: bool) -> Result<(), ClientError> {
+    let cwd = std::env::current_dir()?;
+    let mut cache = Cache::read(&cwd, puzzle.year)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    let input_path = puzzle.data_path("inputs", "txt");
+    let puzzle_path = puzzle.data_path("puzzles", "md");
+
+    let cached = |path: &str| {
+        if force {
+            None
+        } else {
+            cache.get_intact(path).cloned()
+        }
     };
+    let (cached_input, cached_puzzle) = (cached(&input_path), cached(&puzzle_path));
+
+    if cached_input.is_some() && cached_puzzle.is_some() {
+        println!("---");
+    } else {
+        let client = Client::from_env()?;
+
+        let input = match cached_input {
+            Some(_) => None,
+            None => Some((client.input(puzzle)?, client.url(puzzle, "/input"))),
+        };
+        let description = match cached_puzzle {
+            Some(_) => None,
+            None => Some((client.puzzle(puzzle)? + "\n", client.url(puzzle, ""))),
+        };
+
+        // data directories of other years do not exist until their first puzzle is set up.
+        for folder in ["inputs", "puzzles"] {
+            fs::create_dir_all(format!("{}/{folder}", puzzle.year.data_dir()))?;
+        }
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+
+        println!("---");
+        for (path, file, name) in [
+            (&input_path, input, "input"),
+            (&puzzle_path, description, "puzzle"),
+        ] {
+            if let Some((contents, source)) = file {
+                fs::write(path, &contents)?;
+                cache.record(path, contents.as_bytes(), &source, now);
+                println!("🎄 Successfully wrote {name} to \"{path}\".");
+            }
+        }
+
+        cache.store(puzzle.year)?;
+    }
+
+    for (path, entry, name) in [
+        (&input_path, cached_input, "input"),
+        (&puzzle_path, cached_puzzle, "puzzle"),
+    ] {
+        if let Some(entry) = entry {
+            println!(
+                "🎄 Using cached {name} \"{path}\" (fetched {}). Pass --force to download it again.",
+                format_date(entry.fetched_at)
+            );
+        }
+    }
+
+    Ok(())
 }
@@ -0,0 +1,248 @@
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+use crate::day;
+use crate::template::answers::Answers;
+use crate::template::aoc_client::SubmitOutcome;
+use crate::template::registry;
+use crate::template::submissions::Ledger;
+use crate::template::timings::{get_path_for_timings, Timing, Timings};
+use crate::template::{all_days, Day, Puzzle, Year, ANSI_BOLD, ANSI_RESET};
+
+/// Where a day stands, see [`handle`].
+struct DayStatus {
+    puzzle: Puzzle,
+    has_bin: bool,
+    has_input: bool,
+    has_example: bool,
+    has_description: bool,
+    /// `None` if the tests were not run.
+    tests: Option<bool>,
+    answers: Answers,
+    timing: Option<Timing>,
+    /// `None` if the submissions could not be read.
+    ledger: Option<Ledger>,
+}
+
+/// Prints an overview of all days of `year`: which files exist, whether the tests pass, the
+/// recorded answers, the stored timings and what was submitted. Running the tests of every day
+/// takes a while, so they are only run with `run_tests`.
+pub fn handle(year: Year, run_tests: bool) {
+    // days without timings are shown as such, no need to report a missing file.
+    let timings = Timings::read_from_path(&get_path_for_timings(year)).unwrap_or_default();
+
+    let days: Vec<DayStatus> = all_days()
+        .map(|day| status(Puzzle::new(year, day), &timings, run_tests))
+        .collect();
+
+    println!("| Day | Solution | Input | Example | Description | Tests | Answers | Time | Part 1 | Part 2 |");
+    println!("| :---: | :---: | :---: | :---: | :---: | :---: | :---: | :---: | :---: | :---: |");
+
+    for day in &days {
+        println!("{}", format_row(day));
+    }
+
+    let implemented = days.iter().filter(|d| d.has_bin).count();
+    let stars: usize = days
+        .iter()
+        .filter_map(|d| Some((d.puzzle.day, d.ledger.as_ref()?)))
+        .map(|(day, ledger)| {
+            [1, 2]
+                .into_iter()
+                .filter(|part| is_solved(ledger, day, *part))
+                .count()
+        })
+        .sum();
+
+    println!(
+        "\n{ANSI_BOLD}Progress:{ANSI_RESET} {implemented} of {} days implemented, {stars} stars.",
+        days.len()
+    );
+}
+
+fn status(puzzle: Puzzle, timings: &Timings, run_tests: bool) -> DayStatus {
+    let has_bin = registry::get(puzzle).is_some();
+    let exists =
+        |folder: &str, extension: &str| Path::new(&puzzle.data_path(folder, extension)).exists();
+
+    let ledger = Ledger::read(puzzle)
+        .map_err(|e| eprintln!("Failed to read submissions: {e}"))
+        .ok();
+
+    DayStatus {
+        puzzle,
+        has_bin,
+        has_input: exists("inputs", "txt"),
+        has_example: exists("examples", "txt"),
+        has_description: exists("puzzles", "md"),
+        tests: (run_tests && has_bin).then(|| run_bin_tests(puzzle)),
+        answers: Answers::read(puzzle).unwrap_or_default(),
+        timing: timings.data.iter().find(|t| t.day == puzzle.day).cloned(),
+        ledger,
+    }
+}
+
+/// Runs the tests of a day's binary, returns whether they passed.
+fn run_bin_tests(puzzle: Puzzle) -> bool {
+    Command::new("cargo")
+        .args(["test", "--quiet", "--bin", &puzzle.to_string()])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+/// Day 25 has no second puzzle, its second star is awarded together with the first one.
+fn is_solved(ledger: &Ledger, day: Day, part: u8) -> bool {
+    let part = if day == day!(25) { 1 } else { part };
+
+    ledger
+        .submissions
+        .iter()
+        .any(|s| s.part == part && s.outcome == SubmitOutcome::Correct)
+}
+
+/// Submission status of a part: solved, the number of wrong answers, or nothing submitted.
+fn submission_status(ledger: Option<&Ledger>, day: Day, part: u8) -> String {
+    let Some(ledger) = ledger else {
+        return "?".into();
+    };
+
+    if is_solved(ledger, day, part) {
+        return "★".into();
+    }
+
+    let wrong = ledger
+        .submissions
+        .iter()
+        .filter(|s| s.part == part && s.is_wrong())
+        .count();
+
+    if wrong == 0 {
+        "-".into()
+    } else {
+        format!("{wrong} wrong")
+    }
+}
+
+fn format_row(day: &DayStatus) -> String {
+    let check = |present: bool| if present { "✔" } else { "-" };
+
+    let tests = match (day.has_bin, day.tests) {
+        (false, _) => "-",
+        (true, None) => "?",
+        (true, Some(true)) => "✔",
+        (true, Some(false)) => "✖",
+    };
+
+    let answers: Vec<String> = [1, 2]
+        .into_iter()
+        .filter(|part| day.answers.get(*part).is_some())
+        .map(|part| part.to_string())
+        .collect();
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let time = day.timing.as_ref().map_or("-".into(), |timing| {
+        format!(
+            "{:.1?}",
+            Duration::from_nanos(timing.total_nanos.round() as u64)
+        )
+    });
+
+    format!(
+        "| {} | {} | {} | {} | {} | {} | {} | {} | {} | {} |",
+        day.puzzle,
+        check(day.has_bin),
+        check(day.has_input),
+        check(day.has_example),
+        check(day.has_description),
+        tests,
+        if answers.is_empty() {
+            "-".into()
+        } else {
+            answers.join(", ")
+        },
+        time,
+        submission_status(day.ledger.as_ref(), day.puzzle.day, 1),
+        submission_status(day.ledger.as_ref(), day.puzzle.day, 2),
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_row, is_solved, submission_status, DayStatus};
+    use crate::day;
+    use crate::template::answers::Answers;
+    use crate::template::aoc_client::SubmitOutcome;
+    use crate::template::submissions::{Ledger, Submission};
+    use crate::template::{Puzzle, Year};
+
+    fn submission(part: u8, outcome: SubmitOutcome) -> Submission {
+        Submission {
+            part,
+            answer: "1".into(),
+            outcome,
+            timestamp: 0,
+        }
+    }
+
+    #[test]
+    fn summarizes_submissions() {
+        let ledger = Ledger {
+            submissions: vec![
+                submission(1, SubmitOutcome::TooLow),
+                submission(1, SubmitOutcome::Correct),
+                submission(2, SubmitOutcome::TooHigh),
+                submission(2, SubmitOutcome::Incorrect),
+            ],
+        };
+
+        assert_eq!(submission_status(Some(&ledger), day!(3), 1), "★");
+        assert_eq!(submission_status(Some(&ledger), day!(3), 2), "2 wrong");
+        assert_eq!(submission_status(Some(&Ledger::default()), day!(3), 1), "-");
+        assert_eq!(submission_status(None, day!(3), 1), "?");
+    }
+
+    #[test]
+    fn solves_the_last_day_with_its_first_part() {
+        let ledger = Ledger {
+            submissions: vec![submission(1, SubmitOutcome::Correct)],
+        };
+
+        assert!(is_solved(&ledger, day!(25), 2));
+        assert!(!is_solved(&ledger, day!(24), 2));
+        assert_eq!(submission_status(Some(&ledger), day!(25), 2), "★");
+        assert_eq!(
+            submission_status(Some(&Ledger::default()), day!(25), 2),
+            "-"
+        );
+    }
+
+    #[test]
+    fn formats_rows() {
+        let mut answers = Answers::default();
+        answers.set(1, "42");
+
+        let day = DayStatus {
+            puzzle: Puzzle::new(Year::primary(), day!(3)),
+            has_bin: true,
+            has_input: true,
+            has_example: false,
+            has_description: true,
+            tests: None,
+            answers,
+            timing: None,
+            ledger: Some(Ledger::default()),
+        };
+
+        assert_eq!(
+            format_row(&day),
+            "| 03 | ✔ | ✔ | - | ✔ | ? | 1 | - | - | - |"
+        );
+    }
+}
+
+/* -------------------------------------------------------------------------- */
@@ -1,15 +1,16 @@
 use std::process;
 
-use crate::template::{aoc_cli, Day};
+use crate::template::aoc_client::Client;
+use crate::template::Puzzle;
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
+pub fn handle(puzzle: Puzzle) {
+    let description = Client::from_env().and_then(|client| client.puzzle(puzzle));
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
-    };
+    match description {
+        Ok(description) => println!("{description}"),
+        Err(e) => {
+            eprintln!("failed to read puzzle: {e}");
+            process::exit(1);
+        }
+    }
 }
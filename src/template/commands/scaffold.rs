@@ -1,26 +1,115 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
+    fs::{self, File, OpenOptions},
+    io::{ErrorKind, Write},
     process,
 };
 
-use crate::template::Day;
-
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+use crate::template::aoc_client;
+use crate::template::examples::{self, Examples};
+use crate::template::templates::{self, Placeholders};
+use crate::template::Puzzle;
 
 fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
     OpenOptions::new().write(true).create_new(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+/// Arguments of the `solution!` macro for `puzzle`.
+fn solution_args(puzzle: Puzzle) -> String {
+    let day = puzzle.day.into_inner();
+
+    if puzzle.year.is_primary() {
+        day.to_string()
+    } else {
+        format!("year = {}, {day}", puzzle.year)
+    }
+}
+
+/// Expected value of a part's test. Answers that do not fit a `u32`, the smallest return type of
+/// the built-in templates, are left for the user to fill in.
+fn expected_answer(answer: Option<&String>) -> String {
+    match answer.and_then(|answer| answer.parse::<u32>().ok()) {
+        Some(answer) => format!("Some({answer})"),
+        None => "None".into(),
+    }
+}
+
+/// Fills the placeholders of the module template, see [`templates`].
+fn module_contents(puzzle: Puzzle, template: &str, title: String, examples: &Examples) -> String {
+    let part_two_example = if examples.part_two_input.is_some() {
+        "read_file_part(\"examples\", PUZZLE, 2)"
+    } else {
+        "read_file(\"examples\", PUZZLE)"
+    };
+
+    templates::render(
+        template,
+        &Placeholders {
+            puzzle: solution_args(puzzle),
+            day_number: puzzle.day.into_inner(),
+            year: puzzle.year.into_inner(),
+            title,
+            url: aoc_client::url(&aoc_client::base_url_from_env(), puzzle, ""),
+            answers: examples
+                .answers
+                .each_ref()
+                .map(|a| expected_answer(a.as_ref())),
+            part_two_example: part_two_example.into(),
+        },
+    )
+}
+
+/// Writes an example file. Existing examples are kept, they may have been edited by hand.
+fn write_example(path: &str, example: Option<&String>) {
+    let written = safe_create_file(path).and_then(|mut file| match example {
+        Some(example) => file.write_all(example.as_bytes()),
+        None => Ok(()),
+    });
+
+    match (written, example) {
+        (Err(e), _) if e.kind() == ErrorKind::AlreadyExists => {
+            println!("Kept existing example file \"{path}\"");
+        }
+        (Ok(()), Some(_)) => {
+            println!("Created example file \"{path}\" from the puzzle description");
+        }
+        (Ok(()), None) => {
+            println!("Created empty example file \"{path}\"");
+        }
+        (Err(e), _) => {
+            eprintln!("Failed to create example file: {e}");
+            process::exit(1);
+        }
+    }
 }
 
-pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+pub fn handle(puzzle: Puzzle, template: &str) {
+    let input_path = puzzle.data_path("inputs", "txt");
+    let example_path = puzzle.data_path("examples", "txt");
+    let module_path = format!("src/bin/{puzzle}.rs");
+
+    let template = match templates::load(template) {
+        Ok(template) => template,
+        Err(e) => {
+            eprintln!("Failed to load template: {e}");
+            process::exit(1);
+        }
+    };
+
+    // title and examples are taken from the puzzle description if it was downloaded.
+    let markdown = fs::read_to_string(puzzle.data_path("puzzles", "md")).ok();
+    let examples = markdown.as_deref().map(examples::parse).unwrap_or_default();
+    let title = markdown
+        .as_deref()
+        .and_then(templates::title)
+        .unwrap_or_else(|| format!("Day {}", puzzle.day.into_inner()));
+
+    // data directories of other years do not exist until their first puzzle is scaffolded.
+    for folder in ["inputs", "examples"] {
+        if let Err(e) = fs::create_dir_all(format!("{}/{folder}", puzzle.year.data_dir())) {
+            eprintln!("Failed to create {folder} directory: {e}");
+            process::exit(1);
+        }
+    }
 
     let mut file = match safe_create_file(&module_path) {
         Ok(file) => file,
@@ -30,11 +119,7 @@ pub fn handle(day: Day) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    match file.write_all(module_contents(puzzle, &template, title, &examples).as_bytes()) {
         Ok(()) => {
             println!("Created module file \"{}\"", &module_path);
         }
@@ -44,26 +129,24 @@ pub fn handle(day: Day) {
         }
     }
 
-    match create_file(&input_path) {
+    // a downloaded input is kept.
+    match safe_create_file(&input_path) {
         Ok(_) => {
             println!("Created empty input file \"{}\"", &input_path);
         }
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => {}
         Err(e) => {
             eprintln!("Failed to create input file: {e}");
             process::exit(1);
         }
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
-        }
+    write_example(&example_path, examples.input.as_ref());
+    if examples.part_two_input.is_some() {
+        let path = format!("{}/examples/{}-2.txt", puzzle.year.data_dir(), puzzle.day);
+        write_example(&path, examples.part_two_input.as_ref());
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    println!("🎄 Type `cargo solve {puzzle}` to run your solution.");
 }
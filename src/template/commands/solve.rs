@@ -1,9 +1,28 @@
 use std::process::{Command, Stdio};
+use std::time::Duration;
 
-use crate::template::Day;
+use crate::template::Puzzle;
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+/// Which inputs the solution runs on, forwarded to the binary. See [`crate::template::inputs`].
+#[derive(Debug, Default)]
+pub struct InputOptions {
+    /// Path of an input file, `-` for stdin.
+    pub input: Option<String>,
+    /// Name of an alternate input in `data/inputs/<name>`.
+    pub input_set: Option<String>,
+    /// Cross-check the default input and all alternate inputs.
+    pub all_inputs: bool,
+}
+
+pub fn handle(
+    puzzle: Puzzle,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    timeout: Option<Duration>,
+    inputs: InputOptions,
+) {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), puzzle.to_string()];
 
     if dhat {
         cmd_args.extend([
@@ -23,6 +42,25 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push(submit_part.to_string());
     }
 
+    if let Some(timeout) = timeout {
+        cmd_args.push("--timeout".to_string());
+        cmd_args.push(format!("{timeout:?}"));
+    }
+
+    if let Some(input) = inputs.input {
+        cmd_args.push("--input".to_string());
+        cmd_args.push(input);
+    }
+
+    if let Some(input_set) = inputs.input_set {
+        cmd_args.push("--input-set".to_string());
+        cmd_args.push(input_set);
+    }
+
+    if inputs.all_inputs {
+        cmd_args.push("--all-inputs".to_string());
+    }
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
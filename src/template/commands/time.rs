@@ -1,40 +1,187 @@
 use std::collections::HashSet;
+use std::time::Duration;
+use std::{fs, process};
 
-use crate::template::run_multi::run_multi;
-use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::charts;
+use crate::template::compare::{compare, print_report};
+use crate::template::history::{self, Entry};
+use crate::template::run_multi::{run_multi, RunOptions};
+use crate::template::timings::{get_path_for_baseline, is_valid_baseline_name, Timings};
+use crate::template::{all_days, readme_benchmarks, Day, Year};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+/// Options controlling which days are benched and what happens with the results.
+#[derive(Debug, Default)]
+pub struct TimeOptions {
+    /// Bench all days, including days that are already fully benched.
+    pub all: bool,
+    /// Store the timings in `timings.json`, the README and the benchmark history.
+    pub store: bool,
+    /// Also write the HTML report when storing.
+    pub html: bool,
+    pub spawn: bool,
+    pub timeout: Option<Duration>,
+    /// Profile heap usage, see [`RunOptions::memory`].
+    pub memory: bool,
+}
+
+/// Options for comparing a run against a baseline.
+#[derive(Debug, Default)]
+pub struct BaselineOptions {
+    /// Name of the baseline to compare against. Compares against `timings.json` if not set.
+    pub compare_to: Option<String>,
+    /// Name of the baseline to store this run in.
+    pub save_as: Option<String>,
+    /// Exit with a non-zero code if a part got slower by more than this percentage.
+    pub threshold: Option<f64>,
+}
+
+/// Benches all days of `year` (or only `day`).
+pub fn handle(year: Year, day: Option<Day>, options: &TimeOptions, baseline: &BaselineOptions) {
+    for name in [&baseline.compare_to, &baseline.save_as]
+        .into_iter()
+        .flatten()
+    {
+        if !is_valid_baseline_name(name) {
+            eprintln!("Invalid baseline name `{name}`. Use letters, digits, `-` and `_` only.");
+            process::exit(1);
+        }
+    }
+
+    let stored_timings = Timings::read_from_file(year);
+
+    let (baseline_name, baseline_timings) = match &baseline.compare_to {
+        Some(name) => match Timings::read_from_path(&get_path_for_baseline(year, name)) {
+            Ok(timings) => (format!("baseline `{name}`"), timings),
+            Err(e) => {
+                eprintln!("Failed to read baseline: {e}");
+                process::exit(1);
+            }
+        },
+        None => ("stored timings".to_string(), stored_timings.clone()),
+    };
+
+    let is_comparing = baseline.compare_to.is_some() || baseline.threshold.is_some();
 
     let days_to_run = day.map_or_else(
         || {
-            if run_all {
+            if options.all {
                 all_days().collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
+                // days in the baseline are always benched, otherwise there is nothing to compare.
                 all_days()
-                    .filter(|day| !stored_timings.is_day_complete(*day))
+                    .filter(|day| {
+                        !stored_timings.is_day_complete(*day)
+                            || (is_comparing && baseline_timings.has_day(*day))
+                    })
                     .collect()
             }
         },
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let run_options = RunOptions {
+        is_release: true,
+        is_timed: true,
+        spawn: options.spawn,
+        jobs: 1,
+        timeout: options.timeout,
+        memory: options.memory,
+    };
+
+    let Some(timings) = run_multi(year, &days_to_run, run_options) else {
+        eprintln!("Failed to collect timings.");
+        process::exit(1);
+    };
+
+    let comparison = compare(&baseline_timings, &timings);
+
+    if !comparison.deltas.is_empty()
+        || !comparison.missing.is_empty()
+        || baseline.compare_to.is_some()
+    {
+        println!();
+        print_report(&comparison, &baseline_name, baseline.threshold);
+    }
+
+    if options.store {
+        if let Err(e) = fs::create_dir_all(year.data_dir()) {
+            eprintln!("Failed to create data directory of {year}: {e}");
+            process::exit(1);
+        }
+
+        match history::append(year, &Entry::now(timings.clone())) {
+            Ok(()) => println!("Recorded run in benchmark history."),
+            Err(e) => eprintln!("Failed to record run in benchmark history: {e}"),
+        }
 
-    if store {
         let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        merged_timings.store_file(year).unwrap();
+
+        // charts are written first, so the README can link them.
+        if let Err(e) = charts::store_svg(year, &merged_timings) {
+            eprintln!("Failed to store benchmark chart: {e}");
+        }
+
+        if options.html {
+            let history = history::read(year).unwrap_or_else(|e| {
+                eprintln!("Failed to read benchmark history: {e}");
+                vec![]
+            });
+
+            if let Err(e) = charts::store_html(year, &merged_timings, &history) {
+                eprintln!("Failed to store benchmark report: {e}");
+            }
+        }
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
+        match readme_benchmarks::update(year, merged_timings) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
-            Err(_) => {
-                eprintln!("Failed to store updated benchmarks.");
+            Err(e) => {
+                eprintln!("Failed to store updated benchmarks: {e}");
             }
         }
     }
+
+    if let Some(name) = &baseline.save_as {
+        let path = get_path_for_baseline(year, name);
+        let existing = Timings::read_from_path(&path).unwrap_or_default();
+
+        match existing.merge(&timings).store_to_path(&path) {
+            Ok(()) => println!("Stored baseline `{name}`."),
+            Err(e) => eprintln!("Failed to store baseline `{name}`: {e}"),
+        }
+    }
+
+    if let Some(threshold) = baseline.threshold {
+        let regressions = comparison
+            .deltas
+            .iter()
+            .filter(|d| d.is_regression(threshold))
+            .count();
+        let missing = comparison.missing.len();
+
+        if regressions > 0 {
+            eprintln!("\n{regressions} part(s) regressed by more than {threshold}%.");
+        }
+        if missing > 0 {
+            eprintln!("\n{missing} part(s) of the baseline have no timing.");
+        }
+        if regressions > 0 || missing > 0 {
+            process::exit(1);
+        }
+    }
+}
+
+/// Prints how the benchmarks of all days of `year` (or only `day`) developed across stored runs.
+pub fn print_history(year: Year, day: Option<Day>) {
+    match history::read(year) {
+        Ok(entries) => history::print_report(&entries, day),
+        Err(e) => {
+            eprintln!("Failed to read benchmark history: {e}");
+            process::exit(1);
+        }
+    }
 }
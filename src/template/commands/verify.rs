@@ -0,0 +1,104 @@
+use std::process;
+use std::time::Duration;
+
+use crate::template::answers::{get_path_for_answers, verdict, Answers, Verdict};
+use crate::template::run_multi::{run_each, RunOptions};
+use crate::template::{all_days, Day, Puzzle, Year, ANSI_BOLD, ANSI_RESET};
+
+/// Runs solutions of `year` against their real inputs and compares the results with the recorded
+/// answers. With `record`, answers of parts without a recorded answer are stored.
+///
+/// Exits with an error if an answer is wrong. Parts without a recorded answer are reported as
+/// missing, which only fails the verification with `strict`, e.g. to make sure CI checks every part.
+pub fn handle(
+    year: Year,
+    day: Option<Day>,
+    spawn: bool,
+    jobs: usize,
+    timeout: Option<Duration>,
+    record: bool,
+    strict: bool,
+) {
+    let days: Vec<Puzzle> = day
+        .map_or_else(|| all_days().collect(), |day| vec![day])
+        .into_iter()
+        .map(|day| Puzzle::new(year, day))
+        .collect();
+
+    let options = RunOptions {
+        is_release: true,
+        is_timed: false,
+        spawn,
+        jobs,
+        timeout,
+        memory: false,
+    };
+
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+
+    run_each(&days, options, |_, run| {
+        let mut answers = Answers::read(run.puzzle).unwrap_or_else(|e| {
+            eprintln!(
+                "Failed to read \"{}\": {e}",
+                get_path_for_answers(run.puzzle)
+            );
+            Answers::default()
+        });
+
+        let mut has_new_answers = false;
+
+        for part in [1, 2] {
+            let expected = answers.get(part).map(str::to_string);
+            let part_record = run.records.iter().find(|r| r.part == part);
+            let actual = part_record.and_then(|r| r.answer.as_deref());
+
+            // the answer, or why there is none.
+            let got = match (actual, part_record.and_then(|r| r.failure.as_ref())) {
+                (Some(actual), _) => format!("{actual:?}"),
+                (None, Some(failure)) => failure.to_string(),
+                (None, None) => "✖".into(),
+            };
+
+            let line = match verdict(expected.as_deref(), actual) {
+                Verdict::Pass => {
+                    passed += 1;
+                    "PASS".to_string()
+                }
+                Verdict::Fail => {
+                    failed += 1;
+                    format!(
+                        "FAIL (expected {:?}, got {got})",
+                        expected.unwrap_or_default()
+                    )
+                }
+                Verdict::Missing => {
+                    missing += 1;
+
+                    if let (true, Some(actual)) = (record, actual) {
+                        answers.set(part, actual);
+                        has_new_answers = true;
+                        format!("MISSING (recorded {actual:?})")
+                    } else {
+                        format!("MISSING (got {got})")
+                    }
+                }
+            };
+
+            println!("Day {} / Part {part}: {line}", run.puzzle);
+        }
+
+        if has_new_answers {
+            if let Err(e) = answers.store(run.puzzle) {
+                eprintln!("Failed to record answers: {e}");
+            }
+        }
+    });
+
+    println!(
+        "\n{ANSI_BOLD}Verified:{ANSI_RESET} {passed} passed, {failed} failed, {missing} missing."
+    );
+
+    if failed > 0 || (strict && missing > 0) {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,266 @@
+/// Compares benchmark timings against a baseline.
+///
+/// A change is considered significant if the difference of means is large compared to the stored
+/// spread of both runs (Welch's z-score above 1.96, i.e. ~95% confidence). Timings stored without
+/// statistics can not be tested, their significance is unknown.
+use std::time::Duration;
+
+use crate::template::record::PARSE;
+use crate::template::runner::part_label;
+use crate::template::stats::Stats;
+use crate::template::timings::Timings;
+use crate::template::{Day, ANSI_BOLD, ANSI_RESET};
+
+const Z_CRITICAL: f64 = 1.96;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Significance {
+    Significant,
+    NotSignificant,
+    Unknown,
+}
+
+/// The change of a single part between the baseline and the current run.
+#[derive(Clone, Debug)]
+pub struct Delta {
+    pub day: Day,
+    pub part: u8,
+    pub baseline_nanos: f64,
+    pub current_nanos: f64,
+    pub significance: Significance,
+}
+
+impl Delta {
+    pub fn change_nanos(&self) -> f64 {
+        self.current_nanos - self.baseline_nanos
+    }
+
+    pub fn change_percent(&self) -> f64 {
+        if self.baseline_nanos == 0.0 {
+            return 0.0;
+        }
+        self.change_nanos() / self.baseline_nanos * 100.0
+    }
+
+    /// A part regressed if it got slower by more than `threshold` percent and the change can
+    /// not be ruled out as noise.
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.change_percent() > threshold && self.significance != Significance::NotSignificant
+    }
+}
+
+/// The result of [`compare`].
+#[derive(Clone, Debug, Default)]
+pub struct Comparison {
+    pub deltas: Vec<Delta>,
+    /// Parts with a baseline timing but no current timing, e.g. because they failed.
+    pub missing: Vec<(Day, u8)>,
+}
+
+/// Compares all parts, and the parse phase, present in both `baseline` and `current`.
+/// Parts of a day in `current` that only have a baseline timing are reported as missing.
+pub fn compare(baseline: &Timings, current: &Timings) -> Comparison {
+    let mut comparison = Comparison::default();
+
+    for timing in &current.data {
+        let Some(base) = baseline.data.iter().find(|t| t.day == timing.day) else {
+            continue;
+        };
+
+        for part in [PARSE, 1, 2] {
+            let (Some(baseline_nanos), current_nanos) =
+                (base.part_nanos(part), timing.part_nanos(part))
+            else {
+                continue;
+            };
+
+            let Some(current_nanos) = current_nanos else {
+                comparison.missing.push((timing.day, part));
+                continue;
+            };
+
+            comparison.deltas.push(Delta {
+                day: timing.day,
+                part,
+                baseline_nanos,
+                current_nanos,
+                significance: significance(base.part_stats(part), timing.part_stats(part)),
+            });
+        }
+    }
+
+    comparison
+}
+
+fn significance(baseline: Option<&Stats>, current: Option<&Stats>) -> Significance {
+    let (Some(a), Some(b)) = (baseline, current) else {
+        return Significance::Unknown;
+    };
+
+    let standard_error =
+        (a.std_dev.powi(2) / a.samples as f64 + b.std_dev.powi(2) / b.samples as f64).sqrt();
+
+    let difference = (b.mean - a.mean).abs();
+
+    let is_significant = if standard_error == 0.0 {
+        difference > 0.0
+    } else {
+        difference / standard_error > Z_CRITICAL
+    };
+
+    if is_significant {
+        Significance::Significant
+    } else {
+        Significance::NotSignificant
+    }
+}
+
+/// Prints a report with one line per compared or missing part.
+pub fn print_report(comparison: &Comparison, baseline_name: &str, threshold: Option<f64>) {
+    println!("{ANSI_BOLD}Comparison against {baseline_name}{ANSI_RESET}");
+    println!("------");
+
+    if comparison.deltas.is_empty() && comparison.missing.is_empty() {
+        println!("No matching timings in baseline.");
+        return;
+    }
+
+    for delta in &comparison.deltas {
+        let sign = if delta.change_nanos() < 0.0 { "-" } else { "+" };
+
+        let significance = match delta.significance {
+            Significance::Significant => "significant",
+            Significance::NotSignificant => "within noise",
+            Significance::Unknown => "significance unknown",
+        };
+
+        let marker = match threshold {
+            Some(threshold) if delta.is_regression(threshold) => " ✖ regression",
+            _ => "",
+        };
+
+        println!(
+            "Day {} / {}: {:.1?} → {:.1?} ({sign}{:.1?}, {sign}{:.2}%, {significance}){marker}",
+            delta.day,
+            part_label(delta.part),
+            to_duration(delta.baseline_nanos),
+            to_duration(delta.current_nanos),
+            to_duration(delta.change_nanos().abs()),
+            delta.change_percent().abs(),
+        );
+    }
+
+    for (day, part) in &comparison.missing {
+        println!("Day {day} / {}: no timing ✖ missing", part_label(*part));
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn to_duration(nanos: f64) -> Duration {
+    Duration::from_nanos(nanos.round() as u64)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{compare, Significance};
+    use crate::day;
+    use crate::template::stats::Stats;
+    use crate::template::timings::{Timing, Timings};
+
+    fn stats(mean: f64, std_dev: f64) -> Stats {
+        Stats {
+            mean,
+            median: mean,
+            min: mean,
+            max: mean,
+            std_dev,
+            p95: mean,
+            p99: mean,
+            samples: 100,
+            warmup: 10,
+            mild_outliers: 0,
+            severe_outliers: 0,
+        }
+    }
+
+    fn timing(day: u8, part_1: Option<Stats>, part_2: Option<String>) -> Timing {
+        Timing {
+            day: crate::template::Day::new(day).unwrap(),
+            part_1: part_1.as_ref().map(|_| "-".into()),
+            part_2,
+            parse: None,
+            total_nanos: 0.0,
+            parse_stats: None,
+            part_1_stats: part_1,
+            part_2_stats: None,
+            part_1_memory: None,
+            part_2_memory: None,
+        }
+    }
+
+    #[test]
+    fn compares_matching_parts() {
+        let baseline = Timings {
+            data: vec![
+                timing(1, Some(stats(1000.0, 10.0)), Some("2ms".into())),
+                timing(2, Some(stats(1000.0, 10.0)), None),
+            ],
+        };
+        let current = Timings {
+            data: vec![
+                timing(1, Some(stats(1500.0, 10.0)), Some("1ms".into())),
+                timing(3, Some(stats(1000.0, 10.0)), None),
+            ],
+        };
+
+        let comparison = compare(&baseline, &current);
+        let deltas = comparison.deltas;
+        assert_eq!(deltas.len(), 2);
+        assert!(comparison.missing.is_empty());
+
+        assert_eq!(deltas[0].day, day!(1));
+        assert_eq!(deltas[0].part, 1);
+        assert_eq!(deltas[0].change_percent(), 50.0);
+        assert_eq!(deltas[0].significance, Significance::Significant);
+        assert!(deltas[0].is_regression(10.0));
+        assert!(!deltas[0].is_regression(60.0));
+
+        assert_eq!(deltas[1].part, 2);
+        assert_eq!(deltas[1].change_percent(), -50.0);
+        assert_eq!(deltas[1].significance, Significance::Unknown);
+        assert!(!deltas[1].is_regression(0.0));
+    }
+
+    #[test]
+    fn ignores_noise() {
+        let baseline = Timings {
+            data: vec![timing(1, Some(stats(1000.0, 500.0)), None)],
+        };
+        let current = Timings {
+            data: vec![timing(1, Some(stats(1050.0, 500.0)), None)],
+        };
+
+        let deltas = compare(&baseline, &current).deltas;
+        assert_eq!(deltas[0].significance, Significance::NotSignificant);
+        assert!(!deltas[0].is_regression(1.0));
+    }
+
+    #[test]
+    fn reports_missing_parts() {
+        let baseline = Timings {
+            data: vec![
+                timing(1, Some(stats(1000.0, 10.0)), Some("2ms".into())),
+                timing(2, Some(stats(1000.0, 10.0)), None),
+            ],
+        };
+        let current = Timings {
+            data: vec![timing(1, Some(stats(1000.0, 10.0)), None)],
+        };
+
+        let comparison = compare(&baseline, &current);
+        assert_eq!(comparison.deltas.len(), 1);
+        assert_eq!(comparison.missing, vec![(day!(1), 2)]);
+    }
+}
@@ -0,0 +1,147 @@
+/// Examples found in a puzzle description.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Examples {
+    /// Example of part one, and of part two if it has none of its own.
+    pub input: Option<String>,
+    /// Example that only part two uses.
+    pub part_two_input: Option<String>,
+    /// Expected answers of part one and part two for their examples.
+    pub answers: [Option<String>; 2],
+}
+
+const PART_TWO_HEADING: &str = "## --- Part Two ---";
+
+/// Extracts example inputs and their expected answers from a downloaded puzzle description.
+///
+/// Puzzle descriptions show an example in a code block and highlight its answer as emphasized
+/// code, e.g. `` `*142*` ``. The first code block of a part is taken as its example and the last
+/// highlighted value as its answer. Part two usually reuses the example of part one; if it
+/// brings a code block of its own, that one becomes the example of part two (`DD-2.txt`).
+#[must_use]
+pub fn parse(markdown: &str) -> Examples {
+    let (part_one, part_two) = match markdown.find(PART_TWO_HEADING) {
+        Some(i) => (&markdown[..i], Some(&markdown[i..])),
+        None => (markdown, None),
+    };
+
+    let (input, answer_one) = parse_part(part_one);
+    let (part_two_input, answer_two) = part_two.map(parse_part).unwrap_or_default();
+
+    Examples {
+        input,
+        part_two_input,
+        answers: [answer_one, answer_two],
+    }
+}
+
+/// Returns the first code block and the last highlighted value of a part.
+fn parse_part(markdown: &str) -> (Option<String>, Option<String>) {
+    let mut block: Option<String> = None;
+    let mut first_block = None;
+    let mut answer = None;
+
+    for line in markdown.lines() {
+        match (&mut block, line == "```") {
+            (None, true) => block = Some(String::new()),
+            (Some(_), true) => {
+                let contents = block.take();
+                first_block = first_block.or(contents.filter(|b| !b.is_empty()));
+            }
+            (Some(block), false) => {
+                block.push_str(line);
+                block.push('\n');
+            }
+            (None, false) => answer = highlighted(line).pop().or(answer),
+        }
+    }
+
+    (first_block, answer)
+}
+
+/// Values of a line that are emphasized code, written as `` `*value*` `` or `` *`value`* ``.
+fn highlighted(line: &str) -> Vec<String> {
+    let mut values = vec![];
+    let mut rest = line;
+
+    while let Some(start) = [rest.find("`*"), rest.find("*`")]
+        .into_iter()
+        .flatten()
+        .min()
+    {
+        let open = &rest[start..start + 2];
+        let close = if open == "`*" { "*`" } else { "`*" };
+        let after = &rest[start + 2..];
+
+        let Some(len) = after.find(close) else {
+            break;
+        };
+
+        let value = &after[..len];
+        if !value.is_empty() && !value.contains(['*', '`']) {
+            values.push(value.to_string());
+        }
+        rest = &after[len + 2..];
+    }
+
+    values
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{highlighted, parse, Examples};
+
+    const PUZZLE: &str = "## --- Day 1: Trebuchet?! ---
+
+Each line has a `value`, for example:
+
+```
+1abc2
+pqr3stu8vwx
+```
+
+In this example, the values are `12` and `38`. Adding these together produces `*50*`.";
+
+    #[test]
+    fn finds_highlighted_values() {
+        assert_eq!(highlighted("produces `*50*`."), vec!["50"]);
+        assert_eq!(highlighted("*`a`* and `*b*`"), vec!["a", "b"]);
+        assert_eq!(highlighted("*emphasis* and `code`"), Vec::<String>::new());
+    }
+
+    #[test]
+    fn parses_part_one() {
+        assert_eq!(
+            parse(PUZZLE),
+            Examples {
+                input: Some("1abc2\npqr3stu8vwx\n".into()),
+                part_two_input: None,
+                answers: [Some("50".into()), None],
+            }
+        );
+    }
+
+    #[test]
+    fn parses_part_two() {
+        let shared = format!("{PUZZLE}\n\n## --- Part Two ---\n\nNow the sum is `*281*`.");
+        let examples = parse(&shared);
+        assert_eq!(examples.part_two_input, None);
+        assert_eq!(examples.answers, [Some("50".into()), Some("281".into())]);
+
+        let separate = format!(
+            "{PUZZLE}\n\n## --- Part Two ---\n\nFor example:\n\n```\ntwo1nine\n```\n\nThis gives *`29`*."
+        );
+        let examples = parse(&separate);
+        assert_eq!(examples.input, Some("1abc2\npqr3stu8vwx\n".into()));
+        assert_eq!(examples.part_two_input, Some("two1nine\n".into()));
+        assert_eq!(examples.answers[1], Some("29".into()));
+    }
+
+    #[test]
+    fn parses_descriptions_without_examples() {
+        assert_eq!(parse("## --- Day 1: Nothing ---"), Examples::default());
+    }
+}
+
+/* -------------------------------------------------------------------------- */
@@ -0,0 +1,432 @@
+/// Append-only history of benchmark runs, stored in `data/timings-history.jsonl` per year.
+///
+/// Every `cargo time --store` appends one line with the time of the run, the git revision checked
+/// out in the working tree and the timings of all days benched in that run.
+/// `cargo time --history` reports how the time of each day developed across these runs.
+use std::collections::{BTreeSet, HashMap};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use tinyjson::JsonValue;
+
+use crate::template::date::civil_from_timestamp;
+use crate::template::timings::{Timing, Timings};
+use crate::template::{Day, Year, ANSI_BOLD, ANSI_RESET};
+
+/// Number of runs shown in the trend of a day.
+const TREND_WIDTH: usize = 20;
+const TREND_BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// A single benchmark run.
+#[derive(Clone, Debug)]
+pub struct Entry {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    /// The commit checked out when the run was recorded. `None` outside of a git repository.
+    pub revision: Option<String>,
+    pub timings: Timings,
+}
+
+impl Entry {
+    /// Creates an entry for a run that finished just now.
+    pub fn now(timings: Timings) -> Self {
+        Entry {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+            revision: git_revision(),
+            timings,
+        }
+    }
+
+    /// The revision shortened to 7 characters, or the date if the revision is unknown.
+    fn label(&self) -> String {
+        match &self.revision {
+            Some(revision) => revision.chars().take(7).collect(),
+            None => format_date(self.timestamp),
+        }
+    }
+}
+
+#[must_use]
+pub fn get_path_for_history(year: Year) -> String {
+    format!("./{}/timings-history.jsonl", year.data_dir())
+}
+
+/// Appends an entry to the history file of `year`.
+pub fn append(year: Year, entry: &Entry) -> io::Result<()> {
+    let line = JsonValue::from(entry)
+        .stringify()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(get_path_for_history(year))?;
+
+    writeln!(file, "{line}")
+}
+
+/// Reads all entries of the history file of `year`, oldest first.
+/// Returns no entries if there is no history yet.
+pub fn read(year: Year) -> Result<Vec<Entry>, String> {
+    let path = get_path_for_history(year);
+
+    match fs::read_to_string(&path) {
+        Ok(s) => parse(&s).map_err(|e| format!("{path}: {e}")),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+        Err(e) => Err(format!("{path}: {e}")),
+    }
+}
+
+fn parse(s: &str) -> Result<Vec<Entry>, String> {
+    s.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            JsonValue::from_str(line)
+                .map_err(|_| "not valid JSON.".to_string())
+                .and_then(|json| Entry::try_from(&json))
+                .map_err(|e| format!("line {}: {e}", i + 1))
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Reads the commit checked out in the working tree from `.git`, without invoking git.
+pub fn git_revision() -> Option<String> {
+    read_revision(Path::new(".git"))
+}
+
+fn read_revision(git: &Path) -> Option<String> {
+    // worktrees and submodules use a `.git` file pointing to the actual git directory.
+    let git_dir = if git.is_file() {
+        let pointer = fs::read_to_string(git).ok()?;
+        let path = PathBuf::from(pointer.trim().strip_prefix("gitdir:")?.trim());
+        git.parent().unwrap_or(Path::new(".")).join(path)
+    } else {
+        git.to_path_buf()
+    };
+
+    let head = fs::read_to_string(git_dir.join("HEAD")).ok()?;
+
+    let Some(reference) = head.trim().strip_prefix("ref:").map(str::trim) else {
+        // detached HEAD.
+        return Some(head.trim().to_string());
+    };
+
+    // branches of worktrees are stored in the common git directory.
+    let common_dir = fs::read_to_string(git_dir.join("commondir"))
+        .map_or(git_dir.clone(), |dir| git_dir.join(dir.trim()));
+
+    fs::read_to_string(common_dir.join(reference))
+        .map(|revision| revision.trim().to_string())
+        .ok()
+        .or_else(|| {
+            let packed_refs = fs::read_to_string(common_dir.join("packed-refs")).ok()?;
+            find_packed_ref(&packed_refs, reference)
+        })
+}
+
+/// Looks up `reference` in the contents of a `packed-refs` file.
+fn find_packed_ref(packed_refs: &str, reference: &str) -> Option<String> {
+    packed_refs
+        .lines()
+        .filter(|line| !line.starts_with('#') && !line.starts_with('^'))
+        .find_map(|line| {
+            let (revision, name) = line.split_once(' ')?;
+            (name.trim() == reference).then(|| revision.to_string())
+        })
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The time of a day in a single run.
+struct Point<'a> {
+    entry: &'a Entry,
+    total_nanos: f64,
+    /// Which parts were benched. Changes between runs with different parts are not comparable.
+    parts: (bool, bool),
+}
+
+/// The largest change between two consecutive, comparable runs of a day.
+struct Change<'a> {
+    entry: &'a Entry,
+    percent: f64,
+}
+
+fn points(entries: &[Entry], day: Day) -> Vec<Point<'_>> {
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let timing: &Timing = entry.timings.data.iter().find(|t| t.day == day)?;
+
+            (timing.total_nanos > 0.0).then(|| Point {
+                entry,
+                total_nanos: timing.total_nanos,
+                parts: (timing.part_1.is_some(), timing.part_2.is_some()),
+            })
+        })
+        .collect()
+}
+
+/// The total time of `day` in every run that benched it, oldest first.
+pub fn totals(entries: &[Entry], day: Day) -> Vec<f64> {
+    points(entries, day)
+        .iter()
+        .map(|point| point.total_nanos)
+        .collect()
+}
+
+fn largest_change<'a>(points: &[Point<'a>]) -> Option<Change<'a>> {
+    points
+        .windows(2)
+        .filter(|w| w[0].parts == w[1].parts)
+        .map(|w| Change {
+            entry: w[1].entry,
+            percent: (w[1].total_nanos - w[0].total_nanos) / w[0].total_nanos * 100.0,
+        })
+        .max_by(|a, b| a.percent.abs().total_cmp(&b.percent.abs()))
+}
+
+/// Renders values as a sparkline, scaled between their minimum and maximum.
+fn trend(values: &[f64]) -> String {
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+    values
+        .iter()
+        .map(|value| {
+            if max > min {
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let i =
+                    ((value - min) / (max - min) * (TREND_BARS.len() - 1) as f64).round() as usize;
+                TREND_BARS[i]
+            } else {
+                TREND_BARS[0]
+            }
+        })
+        .collect()
+}
+
+/// Prints a table with the trend of every day, or only of `day` if given.
+pub fn print_report(entries: &[Entry], day: Option<Day>) {
+    let days: BTreeSet<Day> = entries
+        .iter()
+        .flat_map(|entry| entry.timings.data.iter().map(|t| t.day))
+        .filter(|d| day.is_none_or(|day| day == *d))
+        .collect();
+
+    let (Some(first), Some(last)) = (entries.first(), entries.last()) else {
+        println!("No benchmark history recorded yet. Run `cargo time --store` to record a run.");
+        return;
+    };
+
+    println!(
+        "{ANSI_BOLD}History{ANSI_RESET} ({} runs, {} – {})",
+        entries.len(),
+        format_date(first.timestamp),
+        format_date(last.timestamp)
+    );
+    println!("------");
+    println!("| Day | Trend | First | Latest | Largest change |");
+    println!("| :---: | :--- | :---: | :---: | :---: |");
+
+    for day in days {
+        let points = points(entries, day);
+
+        let (Some(first), Some(last)) = (points.first(), points.last()) else {
+            continue;
+        };
+
+        let values: Vec<f64> = points.iter().map(|p| p.total_nanos).collect();
+        let recent = &values[values.len().saturating_sub(TREND_WIDTH)..];
+
+        let change = largest_change(&points).map_or("-".into(), |change| {
+            format!(
+                "{:+.1}% in {ANSI_BOLD}{}{ANSI_RESET} ({})",
+                change.percent,
+                change.entry.label(),
+                format_date(change.entry.timestamp)
+            )
+        });
+
+        println!(
+            "| {day} | {} | {} | {} | {change} |",
+            trend(recent),
+            format_nanos(first.total_nanos),
+            format_nanos(last.total_nanos)
+        );
+    }
+}
+
+fn format_nanos(nanos: f64) -> String {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let duration = Duration::from_nanos(nanos.round() as u64);
+    format!("{duration:.1?}")
+}
+
+/// Formats a unix timestamp as `YYYY-MM-DD` (UTC).
+pub(crate) fn format_date(timestamp: u64) -> String {
+    let (year, month, day) = civil_from_timestamp(timestamp);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Entry> for JsonValue {
+    fn from(value: &Entry) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert(
+            "revision".into(),
+            value
+                .revision
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert("timings".into(), JsonValue::from(value.timings.clone()));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Entry {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected entry to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|timestamp| *timestamp as u64)
+            .ok_or("expected entry.timestamp to be a number.")?;
+
+        let revision = match json.get("revision") {
+            None | Some(JsonValue::Null) => None,
+            Some(JsonValue::String(revision)) => Some(revision.clone()),
+            _ => return Err("expected entry.revision to be null or a string.".into()),
+        };
+
+        let timings = Timings::try_from(
+            json.get("timings")
+                .ok_or("expected entry to have key `timings`.")?,
+        )?;
+
+        Ok(Entry {
+            timestamp,
+            revision,
+            timings,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{find_packed_ref, format_date, largest_change, parse, points, trend, Entry};
+    use crate::day;
+    use crate::template::timings::{Timing, Timings};
+    use crate::template::Day;
+
+    fn entry(timestamp: u64, revision: &str, nanos: &[(u8, f64, bool)]) -> Entry {
+        Entry {
+            timestamp,
+            revision: Some(revision.into()),
+            timings: Timings {
+                data: nanos
+                    .iter()
+                    .map(|(day, total_nanos, has_part_2)| Timing {
+                        day: Day::new(*day).unwrap(),
+                        part_1: Some("1ms".into()),
+                        part_2: has_part_2.then(|| "1ms".into()),
+                        parse: None,
+                        total_nanos: *total_nanos,
+                        parse_stats: None,
+                        part_1_stats: None,
+                        part_2_stats: None,
+                        part_1_memory: None,
+                        part_2_memory: None,
+                    })
+                    .collect(),
+            },
+        }
+    }
+
+    #[test]
+    fn round_trips_entries() {
+        let entry = entry(1_700_000_000, "0123456789abcdef", &[(1, 1000.0, true)]);
+        let line = JsonValue::from(&entry).stringify().unwrap();
+
+        let parsed = parse(&format!("{line}\n\n{line}\n")).unwrap();
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0].timestamp, 1_700_000_000);
+        assert_eq!(parsed[0].revision.as_deref(), Some("0123456789abcdef"));
+        assert_eq!(parsed[0].timings.data[0].day, day!(1));
+
+        assert!(parse("{}").unwrap_err().starts_with("line 1:"));
+    }
+
+    #[test]
+    fn finds_packed_refs() {
+        let packed_refs = "# pack-refs with: peeled fully-peeled sorted\n\
+            1111111 refs/heads/main\n\
+            2222222 refs/tags/v1\n\
+            ^3333333\n";
+
+        assert_eq!(
+            find_packed_ref(packed_refs, "refs/heads/main"),
+            Some("1111111".into())
+        );
+        assert_eq!(find_packed_ref(packed_refs, "refs/heads/other"), None);
+    }
+
+    #[test]
+    fn finds_largest_change() {
+        let entries = [
+            entry(0, "aaaaaaa", &[(1, 1000.0, false)]),
+            entry(1, "bbbbbbb", &[(1, 900.0, false)]),
+            // solving part 2 is not a change in performance.
+            entry(2, "ccccccc", &[(1, 5000.0, true)]),
+            entry(3, "ddddddd", &[(1, 2000.0, true)]),
+        ];
+
+        let day_1 = points(&entries, day!(1));
+        let change = largest_change(&day_1).unwrap();
+        assert_eq!(change.entry.label(), "ddddddd");
+        assert_eq!(change.percent, -60.0);
+
+        assert!(largest_change(&day_1[..1]).is_none());
+        assert!(points(&entries, day!(2)).is_empty());
+    }
+
+    #[test]
+    fn renders_trends() {
+        assert_eq!(trend(&[1.0, 8.0, 4.5]), "▁█▅");
+        assert_eq!(trend(&[3.0, 3.0]), "▁▁");
+        assert_eq!(trend(&[]), "");
+    }
+
+    #[test]
+    fn formats_dates() {
+        assert_eq!(format_date(0), "1970-01-01");
+        assert_eq!(format_date(951_782_400), "2000-02-29");
+        assert_eq!(format_date(1_792_368_000), "2026-10-19");
+    }
+}
@@ -0,0 +1,256 @@
+/// Selects the input a solution binary runs on.
+///
+/// By default, solutions read their real input from `data/inputs/DD.txt`, or from the binary image
+/// `data/inputs/DD.icb` written by `cargo convert` if there is no text input. Binaries accept:
+///
+/// - `--input <path>`: reads the input from a file, or from stdin if the path is `-`. Binary
+///   Intcode images are decoded to text, for every kind of input.
+/// - `--input-set <name>`: reads a named alternate input from `data/inputs/<name>/DD.txt` (or
+///   `DD.icb`), e.g. the input of a team member.
+/// - `--all-inputs`: runs the default input and every named input, and compares the results with
+///   the answers of each input (`data/answers/DD.txt` and `data/answers/<name>/DD.txt`).
+use std::fmt::Display;
+use std::io::{self, Read};
+use std::path::Path;
+use std::{env, fs};
+
+use itertools::Itertools;
+
+use crate::cache_program_image;
+use crate::intcode::binary::{is_binary_program, read_binary_program};
+use crate::template::answers::{get_path_for_answers, get_path_for_named_answers, Answers};
+use crate::template::{try_read_file, Puzzle};
+
+/// Where an input is read from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InputSource {
+    /// The real input of the puzzle, `data/inputs/DD.txt` or `data/inputs/DD.icb`.
+    Default,
+    /// An alternate input, `data/inputs/<name>/DD.txt` or `data/inputs/<name>/DD.icb`.
+    Named(String),
+    Path(String),
+    Stdin,
+}
+
+/// The inputs a binary was asked to run on.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InputSelection {
+    Single(InputSource),
+    /// Every input, with the results compared to their answers.
+    CrossCheck,
+}
+
+impl InputSource {
+    pub fn read(&self, puzzle: Puzzle) -> io::Result<String> {
+        match self {
+            InputSource::Default => or_image(
+                try_read_file("inputs", puzzle),
+                &puzzle.data_path("inputs", "icb"),
+            ),
+            InputSource::Named(name) => or_image(
+                fs::read_to_string(get_path_for_named_input(puzzle, name)),
+                &get_path_for_named_image(puzzle, name),
+            ),
+            InputSource::Path(path) => decode_input(fs::read(path)?),
+            InputSource::Stdin => {
+                let mut input = vec![];
+                io::stdin().read_to_end(&mut input)?;
+                decode_input(input)
+            }
+        }
+    }
+
+    /// Where the answers for this input are recorded. Inputs from a path or stdin have none.
+    #[must_use]
+    pub fn answers_path(&self, puzzle: Puzzle) -> Option<String> {
+        match self {
+            InputSource::Default => Some(get_path_for_answers(puzzle)),
+            InputSource::Named(name) => Some(get_path_for_named_answers(puzzle, name)),
+            InputSource::Path(_) | InputSource::Stdin => None,
+        }
+    }
+
+    pub fn answers(&self, puzzle: Puzzle) -> io::Result<Answers> {
+        self.answers_path(puzzle)
+            .map_or_else(|| Ok(Answers::default()), |path| Answers::read_path(&path))
+    }
+}
+
+/// Falls back to the binary image at `image_path` if the text input was not found.
+fn or_image(text: io::Result<String>, image_path: &str) -> io::Result<String> {
+    text.or_else(|e| {
+        if e.kind() == io::ErrorKind::NotFound && Path::new(image_path).exists() {
+            decode_input(fs::read(image_path)?)
+        } else {
+            Err(e)
+        }
+    })
+}
+
+/// Returns the text of an input file. Binary Intcode images are decoded to the program's text, and
+/// the decoded program is added to the parse cache so that loading the text does not parse it.
+fn decode_input(bytes: Vec<u8>) -> io::Result<String> {
+    if !is_binary_program(&bytes) {
+        return String::from_utf8(bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e));
+    }
+
+    let program = read_binary_program(bytes.as_slice())
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    let source = program.iter().join(",");
+
+    cache_program_image(source.clone(), program);
+
+    Ok(source)
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputSource::Default => write!(f, "default input"),
+            InputSource::Named(name) => write!(f, "input {name:?}"),
+            InputSource::Path(path) => write!(f, "\"{path}\""),
+            InputSource::Stdin => write!(f, "stdin"),
+        }
+    }
+}
+
+#[must_use]
+pub fn get_path_for_named_input(puzzle: Puzzle, name: &str) -> String {
+    format!(
+        "{}/inputs/{name}/{}.txt",
+        puzzle.year.data_dir(),
+        puzzle.day
+    )
+}
+
+/// The binary image of a named input, used if there is no text input.
+#[must_use]
+pub fn get_path_for_named_image(puzzle: Puzzle, name: &str) -> String {
+    format!(
+        "{}/inputs/{name}/{}.icb",
+        puzzle.year.data_dir(),
+        puzzle.day
+    )
+}
+
+/// Names of the alternate inputs of `puzzle`, i.e. directories in `data/inputs` that contain an
+/// input for its day.
+#[must_use]
+pub fn named_inputs(puzzle: Puzzle) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(format!("{}/inputs", puzzle.year.data_dir())) else {
+        return vec![];
+    };
+
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .filter(|name| {
+            fs::metadata(get_path_for_named_input(puzzle, name)).is_ok()
+                || fs::metadata(get_path_for_named_image(puzzle, name)).is_ok()
+        })
+        .collect();
+
+    names.sort();
+    names
+}
+
+/// Every input of `puzzle`: the default input, followed by the named inputs.
+#[must_use]
+pub fn all_inputs(puzzle: Puzzle) -> Vec<InputSource> {
+    std::iter::once(InputSource::Default)
+        .chain(named_inputs(puzzle).into_iter().map(InputSource::Named))
+        .collect()
+}
+
+/// The inputs the binary was invoked with, see the module docs.
+pub fn from_args() -> Result<InputSelection, String> {
+    let args: Vec<String> = env::args().collect();
+    parse_args(&args)
+}
+
+fn parse_args(args: &[String]) -> Result<InputSelection, String> {
+    let value = |flag: &str| {
+        args.iter()
+            .position(|arg| arg == flag)
+            .map(|i| args.get(i + 1).ok_or(format!("missing value for {flag}.")))
+            .transpose()
+    };
+
+    let path = value("--input")?;
+    let name = value("--input-set")?;
+    let all = args.iter().any(|arg| arg == "--all-inputs");
+
+    match (path, name, all) {
+        (None, None, false) => Ok(InputSelection::Single(InputSource::Default)),
+        (Some(path), None, false) if path == "-" => Ok(InputSelection::Single(InputSource::Stdin)),
+        (Some(path), None, false) => Ok(InputSelection::Single(InputSource::Path(path.clone()))),
+        (None, Some(name), false) => Ok(InputSelection::Single(InputSource::Named(name.clone()))),
+        (None, None, true) => Ok(InputSelection::CrossCheck),
+        _ => Err("--input, --input-set and --all-inputs can not be combined.".into()),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{decode_input, parse_args, InputSelection, InputSource};
+    use crate::day;
+    use crate::encode_program;
+    use crate::template::{Puzzle, Year};
+
+    fn parse(args: &str) -> Result<InputSelection, String> {
+        let args: Vec<String> = args.split_whitespace().map(String::from).collect();
+        parse_args(&args)
+    }
+
+    #[test]
+    fn parses_input_args() {
+        assert_eq!(
+            parse("01 --time"),
+            Ok(InputSelection::Single(InputSource::Default))
+        );
+        assert_eq!(
+            parse("01 --input -"),
+            Ok(InputSelection::Single(InputSource::Stdin))
+        );
+        assert_eq!(
+            parse("01 --input /tmp/in.txt"),
+            Ok(InputSelection::Single(InputSource::Path(
+                "/tmp/in.txt".into()
+            )))
+        );
+        assert_eq!(
+            parse("01 --input-set alice"),
+            Ok(InputSelection::Single(InputSource::Named("alice".into())))
+        );
+        assert_eq!(parse("01 --all-inputs"), Ok(InputSelection::CrossCheck));
+        assert!(parse("01 --input").is_err());
+        assert!(parse("01 --input a --all-inputs").is_err());
+    }
+
+    #[test]
+    fn decodes_binary_inputs() {
+        let image = encode_program(&vec![1, 0, -3, 99]);
+
+        assert_eq!(decode_input(image).unwrap(), "1,0,-3,99");
+        assert_eq!(decode_input(b"1,2\n".to_vec()).unwrap(), "1,2\n");
+        assert!(decode_input(b"ICBN\x07".to_vec()).is_err());
+    }
+
+    #[test]
+    fn named_answers_paths() {
+        let puzzle = Puzzle::new(Year::primary(), day!(4));
+
+        assert_eq!(
+            InputSource::Named("alice".into()).answers_path(puzzle),
+            Some("data/answers/alice/04.txt".into())
+        );
+        assert_eq!(
+            InputSource::Default.answers_path(puzzle),
+            Some("data/answers/04.txt".into())
+        );
+        assert_eq!(InputSource::Stdin.answers_path(puzzle), None);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
@@ -0,0 +1,144 @@
+//! Heap usage of solution parts, measured by running solutions under dhat (`cargo time --memory`).
+use std::fmt::Display;
+#[cfg(feature = "dhat-heap")]
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Heap usage of a single execution of a part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MemoryStats {
+    /// Maximum number of bytes allocated at any one time.
+    pub peak_bytes: u64,
+    /// Number of allocations over the whole execution.
+    pub total_allocations: u64,
+    /// Number of bytes allocated over the whole execution.
+    pub total_bytes: u64,
+}
+
+impl MemoryStats {
+    /// Heap usage since the running profiler was started.
+    ///
+    /// # Panics
+    /// Panics if no heap profiler is running.
+    #[cfg(feature = "dhat-heap")]
+    #[must_use]
+    pub fn current() -> Self {
+        let stats = dhat::HeapStats::get();
+
+        MemoryStats {
+            peak_bytes: stats.max_bytes as u64,
+            total_allocations: stats.total_blocks,
+            total_bytes: stats.total_bytes,
+        }
+    }
+}
+
+/// Set while a [`HeapProfiler`] runs. dhat allows a single profiler at a time, and a part that
+/// timed out keeps its profiler running until the process exits.
+#[cfg(feature = "dhat-heap")]
+static IS_PROFILING: AtomicBool = AtomicBool::new(false);
+
+/// A running dhat heap profiler, stopped when dropped.
+#[cfg(feature = "dhat-heap")]
+pub struct HeapProfiler {
+    profiler: Option<dhat::Profiler>,
+}
+
+#[cfg(feature = "dhat-heap")]
+impl HeapProfiler {
+    /// Starts profiling, or returns `None` if another profiler is still running.
+    /// If `write_profile` is set, `dhat-heap.json` is written when the profiler stops.
+    #[must_use]
+    pub fn start(write_profile: bool) -> Option<Self> {
+        if IS_PROFILING.swap(true, Ordering::AcqRel) {
+            return None;
+        }
+
+        let profiler = if write_profile {
+            dhat::Profiler::new_heap()
+        } else {
+            dhat::Profiler::builder().testing().build()
+        };
+
+        Some(Self {
+            profiler: Some(profiler),
+        })
+    }
+}
+
+#[cfg(feature = "dhat-heap")]
+impl Drop for HeapProfiler {
+    fn drop(&mut self) {
+        // stop the profiler before allowing the next one to start.
+        drop(self.profiler.take());
+        IS_PROFILING.store(false, Ordering::Release);
+    }
+}
+
+impl Display for MemoryStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "peak {}, {} allocs, {} total",
+            format_bytes(self.peak_bytes),
+            self.total_allocations,
+            format_bytes(self.total_bytes)
+        )
+    }
+}
+
+/// Formats a number of bytes with a binary unit, e.g. `1.5 KiB`.
+#[must_use]
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_bytes, MemoryStats};
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+        assert_eq!(
+            format_bytes(5 * 1024 * 1024 * 1024 * 1024 * 1024),
+            "5120.0 TiB"
+        );
+    }
+
+    #[test]
+    fn formats_stats() {
+        let stats = MemoryStats {
+            peak_bytes: 2048,
+            total_allocations: 12,
+            total_bytes: 512,
+        };
+        assert_eq!(stats.to_string(), "peak 2.0 KiB, 12 allocs, 512 B total");
+    }
+
+    #[cfg(feature = "dhat-heap")]
+    #[test]
+    fn runs_a_single_profiler() {
+        let _profiler = super::HeapProfiler::start(false);
+        assert!(super::HeapProfiler::start(false).is_none());
+    }
+}
@@ -1,68 +1,120 @@
-use std::{env, fs};
+use std::{env, fs, io};
 
-pub mod aoc_cli;
+pub mod aoc_client;
 pub mod commands;
+pub mod inputs;
+pub mod registry;
 pub mod runner;
+pub mod templates;
 
 pub use day::*;
+pub use puzzle::*;
+pub use solution::{ParseResult, Solution};
+pub use year::*;
 
+mod answers;
+mod cache;
+mod charts;
+mod compare;
+mod date;
 mod day;
+mod examples;
+mod history;
+mod memory;
+mod puzzle;
 mod readme_benchmarks;
+mod record;
 mod run_multi;
+mod solution;
+mod stats;
+mod submissions;
 mod timings;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
 /// Helper function that reads a text file to a string.
+/// Accepts a [`Day`] of the primary year or a [`Puzzle`] of any year.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+pub fn read_file(folder: &str, puzzle: impl Into<Puzzle>) -> String {
+    try_read_file(folder, puzzle).expect("could not open input file")
+}
+
+/// Like [`read_file`], but returns an error instead of panicking if the file can not be read.
+///
+/// Downloaded files are checked against the hash recorded when they were fetched; a file that
+/// changed since is reported as [`io::ErrorKind::InvalidData`].
+pub fn try_read_file(folder: &str, puzzle: impl Into<Puzzle>) -> io::Result<String> {
+    let puzzle = puzzle.into();
+    let cwd = env::current_dir()?;
+    let path = puzzle.data_path(folder, "txt");
+    let contents = fs::read_to_string(cwd.join(&path))?;
+
+    cache::Cache::read(&cwd, puzzle.year)
+        .and_then(|cache| cache.verify(&path, contents.as_bytes()))
+        .map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{e} Run `cargo download {puzzle} --force` to fetch it again."),
+            )
+        })?;
+
+    Ok(contents)
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_file_part(folder: &str, puzzle: impl Into<Puzzle>, part: u8) -> String {
+    let puzzle = puzzle.into();
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
-        .join("data")
+        .join(puzzle.year.data_dir())
         .join(folder)
-        .join(format!("{day}-{part}.txt"));
+        .join(format!("{}-{part}.txt", puzzle.day));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constants `DAY` and `PUZZLE` and sets up the input and runner for each part.
+/// Also defines the constant `SOLUTION`, which registers the parts for in-process runners.
 ///
-/// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// The second parameter is the type implementing the day's [`Solution`].
+/// Solutions of a year other than the primary year are declared with a leading `year = YYYY`.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
-    };
-    ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+    (year = $year:expr, $day:expr, $solution:ty) => {
+        $crate::solution!(@impl $crate::year!($year), $day, $solution);
     };
-    ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+    ($day:expr, $solution:ty) => {
+        $crate::solution!(@impl $crate::template::Year::primary(), $day, $solution);
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@impl $year:expr, $day:expr, $solution:ty) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
+        /// The current puzzle, i.e. the current day of the solution's year.
+        #[allow(dead_code)]
+        const PUZZLE: $crate::template::Puzzle = $crate::template::Puzzle::new($year, DAY);
+
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
+        /// This day's solution, collected into the registry of the main binary.
+        #[allow(dead_code)]
+        pub const SOLUTION: $crate::template::registry::Entry = $crate::template::registry::Entry {
+            puzzle: PUZZLE,
+            parts: <$solution as $crate::template::Solution>::PARTS,
+            run: |input, is_timed, timeout| {
+                $crate::template::runner::record::<$solution>(input, PUZZLE, is_timed, timeout)
+            },
+        };
+
         fn main() {
-            use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            $crate::template::runner::run::<$solution>(PUZZLE);
         }
     };
 }
@@ -0,0 +1,169 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+#[cfg(feature = "today")]
+use chrono::{Datelike, FixedOffset, Utc};
+
+use crate::template::{Day, Year};
+
+#[cfg(feature = "today")]
+const SERVER_UTC_OFFSET: i32 = -5;
+
+/// A puzzle of a specific event, identified by its [`Year`] and [`Day`].
+///
+/// # Display
+/// This value displays as the name of the puzzle's solution bin: the two digit day for puzzles of
+/// the primary year, the year and day separated by a dash otherwise.
+///
+/// ```
+/// # use advent_of_code::template::{Day, Puzzle, Year};
+/// let day = Day::new(8).unwrap();
+/// assert_eq!(Puzzle::new(Year::primary(), day).to_string(), "08");
+/// assert_eq!(Puzzle::new(Year::new(2015).unwrap(), day).to_string(), "2015-08");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Puzzle {
+    pub year: Year,
+    pub day: Day,
+}
+
+impl Puzzle {
+    pub const fn new(year: Year, day: Day) -> Self {
+        Self { year, day }
+    }
+
+    /// Path of this puzzle's file in a data folder, e.g. `data/2020/inputs/05.txt`.
+    #[must_use]
+    pub fn data_path(self, folder: &str, extension: &str) -> String {
+        format!("{}/{folder}/{}.{extension}", self.year.data_dir(), self.day)
+    }
+
+    /// Parses `DD` as a day of `year`, or `YYYY-DD` as a day of an explicit year.
+    pub fn parse(s: &str, year: Year) -> Result<Self, PuzzleFromStrError> {
+        let (year, day) = match s.split_once('-') {
+            Some((year, day)) => (year.parse().map_err(|_| PuzzleFromStrError)?, day),
+            None => (year, s),
+        };
+
+        let day = day.parse().map_err(|_| PuzzleFromStrError)?;
+        Ok(Self::new(year, day))
+    }
+}
+
+#[cfg(feature = "today")]
+impl Puzzle {
+    /// Returns the puzzle of the current day if it's between the 1st and the 25th of december,
+    /// `None` otherwise.
+    pub fn today() -> Option<Self> {
+        let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
+        let today = Utc::now().with_timezone(&offset);
+        if today.month() == 12 && today.day() <= 25 {
+            Some(Self::new(
+                Year::new(u16::try_from(today.year()).ok()?)?,
+                Day::new(u8::try_from(today.day()).ok()?)?,
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+/// Days without a year refer to the primary year.
+impl From<Day> for Puzzle {
+    fn from(day: Day) -> Self {
+        Self::new(Year::primary(), day)
+    }
+}
+
+impl Display for Puzzle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.year.is_primary() {
+            write!(f, "{}", self.day)
+        } else {
+            write!(f, "{}-{}", self.year, self.day)
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Puzzle {
+    type Err = PuzzleFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s, Year::primary())
+    }
+}
+
+/// An error which can be returned when parsing a [`Puzzle`].
+#[derive(Debug)]
+pub struct PuzzleFromStrError;
+
+impl Error for PuzzleFromStrError {}
+
+impl Display for PuzzleFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(
+            "expecting a day number between 1 and 25, optionally prefixed by a year (`YYYY-DD`)",
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Puzzle;
+    use crate::template::Year;
+    use crate::{day, year};
+
+    #[test]
+    fn parses_puzzles() {
+        let primary = Year::primary();
+
+        assert_eq!(
+            "5".parse::<Puzzle>().ok(),
+            Some(Puzzle::new(primary, day!(5)))
+        );
+        assert_eq!(
+            "2015-05".parse::<Puzzle>().ok(),
+            Some(Puzzle::new(year!(2015), day!(5)))
+        );
+        assert_eq!(
+            Puzzle::parse("12", year!(2016)).ok(),
+            Some(Puzzle::new(year!(2016), day!(12)))
+        );
+        assert!("2015-26".parse::<Puzzle>().is_err());
+        assert!("1999-01".parse::<Puzzle>().is_err());
+        assert!("2015-".parse::<Puzzle>().is_err());
+    }
+
+    #[test]
+    fn displays_as_bin_name() {
+        let puzzle = Puzzle::from(day!(3));
+        assert_eq!(puzzle.to_string(), "03");
+        assert_eq!(puzzle.to_string().parse::<Puzzle>().ok(), Some(puzzle));
+
+        let other = Year::__new_unchecked(Year::primary().into_inner() + 1);
+        let puzzle = Puzzle::new(other, day!(3));
+        assert_eq!(puzzle.to_string(), format!("{other}-03"));
+        assert_eq!(puzzle.to_string().parse::<Puzzle>().ok(), Some(puzzle));
+    }
+
+    #[test]
+    fn data_paths() {
+        assert_eq!(
+            Puzzle::from(day!(7)).data_path("inputs", "txt"),
+            "data/inputs/07.txt"
+        );
+
+        let other = Year::__new_unchecked(Year::primary().into_inner() + 1);
+        assert_eq!(
+            Puzzle::new(other, day!(7)).data_path("examples", "txt"),
+            format!("data/{other}/examples/07.txt")
+        );
+    }
+}
+
+/* -------------------------------------------------------------------------- */
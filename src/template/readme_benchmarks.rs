@@ -1,12 +1,25 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
+use std::path::Path;
 use std::{fs, io};
 
-use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::charts::{get_path_for_html, get_path_for_svg};
+use crate::template::registry;
+use crate::template::timings::{Timing, Timings};
+use crate::template::{Day, Puzzle, Year};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
+/// Marker that delimits the benchmark table of `year`.
+/// Tables of years other than the primary year carry the year in their marker.
+fn marker(year: Year) -> String {
+    if year.is_primary() {
+        MARKER.into()
+    } else {
+        format!("<!--- benchmarking table {year} --->")
+    }
+}
+
 #[derive(Debug)]
 pub enum Error {
     Parser(String),
@@ -19,18 +32,27 @@ impl From<std::io::Error> for Error {
     }
 }
 
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(s) => write!(f, "{s}"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
 pub struct TablePosition {
     pos_start: usize,
     pos_end: usize,
 }
 
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(puzzle: Puzzle) -> String {
+    format!("./src/bin/{puzzle}.rs")
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -51,47 +73,149 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+/// Days of `year` that have a solution, their rows link to the solution's source.
+fn implemented_days(year: Year) -> Vec<Day> {
+    registry::all()
+        .iter()
+        .filter(|solution| solution.puzzle.year == year)
+        .map(|solution| solution.puzzle.day)
+        .collect()
+}
+
+/// Links to the benchmark charts that have been written, see [`crate::template::charts`].
+fn chart_links(year: Year) -> Vec<String> {
+    let mut links = vec![];
+
+    let svg_path = get_path_for_svg(year);
+    if Path::new(&svg_path).exists() {
+        links.push(format!("![Benchmark chart]({svg_path})"));
+    }
+
+    let html_path = get_path_for_html(year);
+    if Path::new(&html_path).exists() {
+        links.push(format!("[Full benchmark report]({html_path})"));
+    }
+
+    links
+}
+
+fn construct_table(
+    prefix: &str,
+    year: Year,
+    timings: Timings,
+    total_millis: f64,
+    implemented: &[Day],
+    links: &[String],
+) -> String {
+    let marker = marker(year);
+
+    let header = if year.is_primary() {
+        format!("{prefix} Benchmarks")
+    } else {
+        format!("{prefix} {year} Benchmarks")
+    };
+
+    // memory columns are only shown once a day was profiled with `cargo time --memory`.
+    let has_memory = timings.data.iter().any(Timing::has_memory);
+    // the parse column is only shown once a day parses ahead of its parts.
+    let has_parse = timings.data.iter().any(|t| t.parse.is_some());
+
+    let mut lines: Vec<String> = vec![marker.clone(), header, String::new()];
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    let mut columns = vec!["Day"];
+    if has_parse {
+        columns.push("Parse");
+    }
+    columns.extend(["Part 1", "Part 2"]);
+    if has_memory {
+        columns.extend(["Part 1 (memory)", "Part 2 (memory)"]);
+    }
+
+    lines.push(format!("| {} |", columns.join(" | ")));
+    // keeps the alignment row of existing tables unchanged.
+    let alignments: Vec<&str> = columns
+        .iter()
+        .map(|c| if *c == "Part 2" { ":---: " } else { ":---:" })
+        .collect();
+    lines.push(format!("| {} |", alignments.join(" | ")));
 
     for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+        let mut line = if implemented.contains(&timing.day) {
+            let path = get_path_for_bin(Puzzle::new(year, timing.day));
+            format!("| [Day {}]({}) |", timing.day.into_inner(), path)
+        } else {
+            format!("| Day {} |", timing.day.into_inner())
+        };
+
+        if has_parse {
+            line.push_str(&format!(" `{}` |", timing.parse.as_deref().unwrap_or("-")));
+        }
+
+        line.push_str(&format!(
+            " `{}` | `{}` |",
+            timing.part_1.as_deref().unwrap_or("-"),
+            timing.part_2.as_deref().unwrap_or("-")
         ));
+
+        if has_memory {
+            for part in [1, 2] {
+                match timing.part_memory(part) {
+                    Some(memory) => line.push_str(&format!(" `{memory}` |")),
+                    None => line.push_str(" `-` |"),
+                }
+            }
+        }
+
+        lines.push(line);
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+
+    for link in links {
+        lines.push(String::new());
+        lines.push(link.clone());
+    }
+
+    lines.push(marker);
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+fn update_content(
+    s: &mut String,
+    year: Year,
+    timings: Timings,
+    total_millis: f64,
+    implemented: &[Day],
+    links: &[String],
+) -> Result<(), Error> {
+    let marker = marker(year);
+
+    // tables of other years are added below the existing content on their first update.
+    if !year.is_primary() && !s.contains(&marker) {
+        s.push_str(&format!("\n{marker}\n{marker}\n"));
+    }
+
+    let positions = locate_table(s, &marker)?;
+    let table = construct_table("##", year, timings, total_millis, implemented, links);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
+/// Updates the benchmark table of `year` in the README.
+pub fn update(year: Year, timings: Timings) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(
+        &mut readme,
+        year,
+        timings,
+        total_millis,
+        &implemented_days(year),
+        &chart_links(year),
+    )?;
     fs::write(path, &readme)?;
     Ok(())
 }
@@ -99,7 +223,14 @@ pub fn update(timings: Timings) -> Result<(), Error> {
 #[cfg(feature = "test_lib")]
 mod tests {
     use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use crate::{
+        day, template::memory::MemoryStats, template::timings::Timing, template::timings::Timings,
+        template::Day, template::Year,
+    };
+
+    fn implemented() -> Vec<Day> {
+        vec![day!(1), day!(2), day!(4)]
+    }
 
     fn get_mock_timings() -> Timings {
         Timings {
@@ -108,19 +239,37 @@ mod tests {
                     day: day!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    parse: None,
                     total_nanos: 3e+10,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    parse: None,
                     total_nanos: 7e+10,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
+                    parse: None,
                     total_nanos: 9e+10,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
                 },
             ],
         }
@@ -130,36 +279,162 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            Year::primary(),
+            get_mock_timings(),
+            190.0,
+            &implemented(),
+            &[],
+        )
+        .unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            Year::primary(),
+            get_mock_timings(),
+            190.0,
+            &implemented(),
+            &[],
+        )
+        .unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            Year::primary(),
+            get_mock_timings(),
+            190.0,
+            &implemented(),
+            &[],
+        )
+        .unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            Year::primary(),
+            get_mock_timings(),
+            190.0,
+            &implemented(),
+            &[],
+        )
+        .unwrap();
+        update_content(
+            &mut s,
+            Year::primary(),
+            get_mock_timings(),
+            190.0,
+            &implemented(),
+            &[],
+        )
+        .unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
 
+    #[test]
+    fn links_charts() {
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        let links = [
+            "![Benchmark chart](./data/benchmarks.svg)".to_string(),
+            "[Full benchmark report](./data/benchmarks.html)".to_string(),
+        ];
+        update_content(
+            &mut s,
+            Year::primary(),
+            get_mock_timings(),
+            190.0,
+            &implemented(),
+            &links,
+        )
+        .unwrap();
+
+        let lines: Vec<&str> = s.lines().collect();
+        assert_eq!(
+            lines[lines.len() - 5..],
+            [
+                "",
+                "![Benchmark chart](./data/benchmarks.svg)",
+                "",
+                "[Full benchmark report](./data/benchmarks.html)",
+                MARKER
+            ]
+        );
+    }
+
+    #[test]
+    fn format_memory_columns() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_2_memory = Some(MemoryStats {
+            peak_bytes: 2048,
+            total_allocations: 12,
+            total_bytes: 4096,
+        });
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, Year::primary(), timings, 190.0, &implemented(), &[]).unwrap();
+
+        let lines: Vec<&str> = s.lines().collect();
+        assert_eq!(
+            lines[3],
+            "| Day | Part 1 | Part 2 | Part 1 (memory) | Part 2 (memory) |"
+        );
+        assert_eq!(
+            lines[5],
+            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` | `-` | `peak 2.0 KiB, 12 allocs, 4.0 KiB total` |"
+        );
+        assert_eq!(
+            lines[6],
+            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` | `-` | `-` |"
+        );
+    }
+
+    #[test]
+    fn format_parse_column() {
+        let mut timings = get_mock_timings();
+        timings.data[1].parse = Some("5ms".into());
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, Year::primary(), timings, 190.0, &implemented(), &[]).unwrap();
+
+        let lines: Vec<&str> = s.lines().collect();
+        assert_eq!(lines[3], "| Day | Parse | Part 1 | Part 2 |");
+        assert_eq!(lines[4], "| :---: | :---: | :---: | :---:  |");
+        assert_eq!(
+            lines[5],
+            "| [Day 1](./src/bin/01.rs) | `-` | `10ms` | `20ms` |"
+        );
+        assert_eq!(
+            lines[6],
+            "| [Day 2](./src/bin/02.rs) | `5ms` | `30ms` | `40ms` |"
+        );
+    }
+
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            Year::primary(),
+            get_mock_timings(),
+            190.0,
+            &implemented(),
+            &[],
+        )
+        .unwrap();
         let expected = [
             "foo",
             "bar",
@@ -179,4 +454,54 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn links_implemented_days() {
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(
+            &mut s,
+            Year::primary(),
+            get_mock_timings(),
+            190.0,
+            &[day!(2)],
+            &[],
+        )
+        .unwrap();
+
+        assert!(s.contains("| Day 1 | `10ms` | `20ms` |"));
+        assert!(s.contains("| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |"));
+    }
+
+    #[test]
+    fn adds_tables_of_other_years() {
+        let other = Year::new(Year::primary().into_inner() + 1).unwrap();
+        let mut s = format!("foo\n{}\n{}\n", MARKER, MARKER);
+
+        update_content(
+            &mut s,
+            other,
+            get_mock_timings(),
+            190.0,
+            &implemented(),
+            &[],
+        )
+        .unwrap();
+        update_content(
+            &mut s,
+            other,
+            get_mock_timings(),
+            190.0,
+            &implemented(),
+            &[],
+        )
+        .unwrap();
+
+        let marker = format!("<!--- benchmarking table {other} --->");
+        assert_eq!(s.matches(MARKER).count(), 2);
+        assert_eq!(s.matches(&marker).count(), 2);
+        assert!(s.contains(&format!("## {other} Benchmarks")));
+        assert!(s.contains(&format!(
+            "| [Day 1](./src/bin/{other}-01.rs) | `10ms` | `20ms` |"
+        )));
+    }
 }
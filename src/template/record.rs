@@ -0,0 +1,375 @@
+/// Machine-readable results of solution binaries.
+///
+/// When a solution binary is invoked with `--format json`, it prints one single-line JSON record
+/// per part to stdout instead of the human-readable `Part N: ...` lines:
+///
+/// ```text
+/// {"type":"part","year":"2019","day":"01","part":1,"status":"solved","answer":"42","duration_nanos":1200,"samples":10,"stats":{...}}
+/// ```
+///
+/// `answer` and `stats` are `null` for unsolved parts and untimed runs respectively.
+/// Parts that timed out or panicked have the status `timeout` or `panic`, the latter with a `message`.
+/// Parts of a binary that could not be run at all have the status `error` and a `message`.
+/// Binaries built with `dhat-heap` add the heap usage of the part as `memory`.
+///
+/// Solutions that parse their input ahead of the parts print a record of the parse phase first.
+/// It has the type `parse`, the part `0`, the status `parsed` and never an answer.
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use tinyjson::JsonValue;
+
+use crate::template::memory::MemoryStats;
+use crate::template::stats::Stats;
+use crate::template::{Day, Year};
+
+const RECORD_TYPE: &str = "part";
+const PARSE_RECORD_TYPE: &str = "parse";
+
+/// Part number of the record of a solution's parse phase, see [`crate::template::Solution::parse`].
+pub const PARSE: u8 = 0;
+
+/// The outcome of running a single part.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartRecord {
+    pub year: Year,
+    pub day: Day,
+    pub part: u8,
+    /// `None` if the solution did not return an answer.
+    pub answer: Option<String>,
+    /// Mean duration when benched, the duration of the single run otherwise.
+    pub duration_nanos: f64,
+    pub stats: Option<Stats>,
+    /// Set if the part did not run to completion.
+    pub failure: Option<Failure>,
+    /// Heap usage of the part, only measured by binaries built with `dhat-heap`.
+    pub memory: Option<MemoryStats>,
+}
+
+/// Why a part did not run to completion.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Failure {
+    /// The part exceeded the configured timeout.
+    Timeout,
+    /// The part panicked with the given message.
+    Panic(String),
+    /// The part could not be run, e.g. because the input could not be parsed or the solution
+    /// binary failed to build.
+    Error(String),
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::Timeout => write!(f, "TIMEOUT"),
+            Failure::Panic(message) => write!(f, "PANIC: {message}"),
+            Failure::Error(message) => write!(f, "ERROR: {message}"),
+        }
+    }
+}
+
+impl PartRecord {
+    pub fn is_solved(&self) -> bool {
+        self.answer.is_some()
+    }
+
+    pub fn is_parse(&self) -> bool {
+        self.part == PARSE
+    }
+
+    pub fn samples(&self) -> usize {
+        self.stats.as_ref().map_or(1, |s| s.samples)
+    }
+
+    /// Serializes the record to a single line of JSON.
+    pub fn to_json_line(&self) -> String {
+        JsonValue::from(self)
+            .stringify()
+            .expect("records only contain finite numbers")
+    }
+
+    /// Parses a line printed by [`PartRecord::to_json_line`].
+    /// Returns `None` for any other output, e.g. debug prints of a solution.
+    pub fn parse_line(line: &str) -> Option<Self> {
+        if !line.starts_with('{') {
+            return None;
+        }
+
+        PartRecord::try_from(&JsonValue::from_str(line).ok()?).ok()
+    }
+}
+
+impl From<&PartRecord> for JsonValue {
+    fn from(value: &PartRecord) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        let status = match &value.failure {
+            Some(Failure::Timeout) => "timeout",
+            Some(Failure::Panic(_)) => "panic",
+            Some(Failure::Error(_)) => "error",
+            None if value.is_parse() => "parsed",
+            None if value.is_solved() => "solved",
+            None => "unsolved",
+        };
+
+        let message = match &value.failure {
+            Some(Failure::Panic(message) | Failure::Error(message)) => {
+                JsonValue::String(message.clone())
+            }
+            _ => JsonValue::Null,
+        };
+
+        let record_type = if value.is_parse() {
+            PARSE_RECORD_TYPE
+        } else {
+            RECORD_TYPE
+        };
+
+        map.insert("type".into(), JsonValue::String(record_type.into()));
+        map.insert("year".into(), JsonValue::String(value.year.to_string()));
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("status".into(), JsonValue::String(status.into()));
+        map.insert("message".into(), message);
+        map.insert(
+            "answer".into(),
+            value
+                .answer
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert(
+            "duration_nanos".into(),
+            JsonValue::Number(value.duration_nanos),
+        );
+        map.insert("samples".into(), JsonValue::Number(value.samples() as f64));
+        map.insert(
+            "stats".into(),
+            value
+                .stats
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+        map.insert(
+            "memory".into(),
+            value
+                .memory
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartRecord {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected record to be a JSON object.")?;
+
+        let parts: &[u8] = match json
+            .get("type")
+            .and_then(|v| v.get::<String>())
+            .map(String::as_str)
+        {
+            Some(RECORD_TYPE) => &[1, 2],
+            Some(PARSE_RECORD_TYPE) => &[PARSE],
+            _ => return Err("expected record.type to be `part` or `parse`.".into()),
+        };
+
+        let year = json
+            .get("year")
+            .and_then(|v| v.get::<String>())
+            .and_then(|year| Year::from_str(year).ok())
+            .ok_or("expected record.year to be a year.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("expected record.day to be a day.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|part| *part as u8)
+            .filter(|part| parts.contains(part))
+            .ok_or("expected record.part to match record.type.")?;
+
+        let answer = match json.get("answer") {
+            Some(JsonValue::Null) => None,
+            Some(JsonValue::String(answer)) => Some(answer.clone()),
+            _ => return Err("expected record.answer to be null or a string.".into()),
+        };
+
+        let duration_nanos = json
+            .get("duration_nanos")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("expected record.duration_nanos to be a number.")?;
+
+        let message = || {
+            json.get("message")
+                .and_then(|v| v.get::<String>())
+                .cloned()
+                .unwrap_or_default()
+        };
+
+        let failure = match json.get("status").and_then(|v| v.get::<String>()) {
+            Some(status) if status == "timeout" => Some(Failure::Timeout),
+            Some(status) if status == "panic" => Some(Failure::Panic(message())),
+            Some(status) if status == "error" => Some(Failure::Error(message())),
+            _ => None,
+        };
+
+        let stats = match json.get("stats") {
+            None | Some(JsonValue::Null) => None,
+            Some(v) => Some(Stats::try_from(v)?),
+        };
+
+        let memory = match json.get("memory") {
+            None | Some(JsonValue::Null) => None,
+            Some(v) => Some(MemoryStats::try_from(v)?),
+        };
+
+        Ok(PartRecord {
+            year,
+            day,
+            part,
+            answer,
+            duration_nanos,
+            stats,
+            failure,
+            memory,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Failure, PartRecord, PARSE};
+    use crate::template::memory::MemoryStats;
+    use crate::template::stats::Stats;
+    use crate::{day, year};
+
+    fn stats() -> Stats {
+        Stats {
+            mean: 1500.0,
+            median: 1400.0,
+            min: 1000.0,
+            max: 9000.0,
+            std_dev: 300.0,
+            p95: 2000.0,
+            p99: 3000.0,
+            samples: 100,
+            warmup: 10,
+            mild_outliers: 2,
+            severe_outliers: 1,
+        }
+    }
+
+    #[test]
+    fn round_trips_records() {
+        let records = [
+            PartRecord {
+                year: year!(2019),
+                day: day!(1),
+                part: 1,
+                answer: Some("1 (2ns @ 3 samples)".into()),
+                duration_nanos: 1500.0,
+                stats: Some(stats()),
+                failure: None,
+                memory: None,
+            },
+            PartRecord {
+                year: year!(2020),
+                day: day!(25),
+                part: 2,
+                answer: Some("#..#\n.##.\n".into()),
+                duration_nanos: 10.0,
+                stats: None,
+                failure: None,
+                memory: None,
+            },
+            PartRecord {
+                year: year!(2019),
+                day: day!(3),
+                part: 2,
+                answer: None,
+                duration_nanos: 0.0,
+                stats: None,
+                failure: None,
+                memory: None,
+            },
+            PartRecord {
+                year: year!(2019),
+                day: day!(7),
+                part: PARSE,
+                answer: None,
+                duration_nanos: 800.0,
+                stats: Some(stats()),
+                failure: None,
+                memory: None,
+            },
+            PartRecord {
+                year: year!(2019),
+                day: day!(7),
+                part: 1,
+                answer: None,
+                duration_nanos: 1e9,
+                stats: None,
+                failure: Some(Failure::Timeout),
+                memory: None,
+            },
+            PartRecord {
+                year: year!(2019),
+                day: day!(7),
+                part: 2,
+                answer: None,
+                duration_nanos: 1200.0,
+                stats: None,
+                failure: Some(Failure::Panic("index out of bounds: \"len\" is 0".into())),
+                memory: Some(MemoryStats {
+                    peak_bytes: 4096,
+                    total_allocations: 7,
+                    total_bytes: 8192,
+                }),
+            },
+            PartRecord {
+                year: year!(2019),
+                day: day!(8),
+                part: 1,
+                answer: None,
+                duration_nanos: 0.0,
+                stats: None,
+                failure: Some(Failure::Error("could not compile `advent_of_code`.".into())),
+                memory: None,
+            },
+        ];
+
+        for record in records {
+            let line = record.to_json_line();
+            assert!(!line.contains('\n'));
+            assert_eq!(PartRecord::parse_line(&line), Some(record));
+        }
+    }
+
+    #[test]
+    fn ignores_other_output() {
+        assert_eq!(PartRecord::parse_line("Part 1: 42 (1ms)"), None);
+        assert_eq!(PartRecord::parse_line("{ not json"), None);
+        assert_eq!(PartRecord::parse_line(r#"{"type":"debug"}"#), None);
+        assert_eq!(
+            PartRecord::parse_line(
+                r#"{"type":"parse","year":"2019","day":"01","part":1,"answer":null,"duration_nanos":1}"#
+            ),
+            None
+        );
+    }
+}
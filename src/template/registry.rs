@@ -0,0 +1,43 @@
+/// Table of solutions linked into the main binary.
+///
+/// Every `solution!` invocation defines a `SOLUTION` constant describing its parts. The build
+/// script includes all `src/bin/DD.rs` and `src/bin/YYYY-DD.rs` files as modules of the main binary and collects these
+/// constants into a table, which `main` installs on startup. Runners can then call solutions
+/// directly instead of spawning `cargo run --bin DD` for every day, and tooling can tell which days
+/// are implemented without looking for their files.
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use crate::template::record::PartRecord;
+use crate::template::Puzzle;
+
+/// Parses the given input and runs all parts on it, benching them if the flag is set.
+/// Parts are given up if they do not finish within `timeout`.
+pub type SolutionRunner =
+    fn(input: &str, is_timed: bool, timeout: Option<Duration>) -> Vec<PartRecord>;
+
+/// A single day's solution, see [`crate::template::Solution`].
+#[derive(Clone, Copy)]
+pub struct Entry {
+    pub puzzle: Puzzle,
+    /// The parts the solution implements.
+    pub parts: &'static [u8],
+    pub run: SolutionRunner,
+}
+
+static SOLUTIONS: OnceLock<&'static [Entry]> = OnceLock::new();
+
+/// Makes `solutions` available to runners. Only the first call has an effect.
+pub fn install(solutions: &'static [Entry]) {
+    let _ = SOLUTIONS.set(solutions);
+}
+
+/// Returns all installed solutions.
+pub fn all() -> &'static [Entry] {
+    SOLUTIONS.get().copied().unwrap_or_default()
+}
+
+/// Returns the installed solution for `puzzle`, if any.
+pub fn get(puzzle: Puzzle) -> Option<&'static Entry> {
+    all().iter().find(|s| s.puzzle == puzzle)
+}
@@ -1,40 +1,124 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::HashSet,
+    fmt::Display,
+    io,
+    process::ExitStatus,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{Day, Puzzle, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
     all_days,
+    inputs::InputSource,
+    record::{Failure, PartRecord},
+    registry::{self, Entry},
+    runner::part_label,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+/// Controls how `run_multi` executes solutions.
+#[derive(Clone, Copy, Debug)]
+pub struct RunOptions {
+    /// Build spawned solution binaries in release mode.
+    pub is_release: bool,
+    pub is_timed: bool,
+    /// Run every day in its own `cargo run --bin DD` process instead of calling the solutions
+    /// linked into this binary.
+    pub spawn: bool,
+    /// Number of days to run concurrently. Ignored for timed runs, which always run sequentially.
+    pub jobs: usize,
+    /// Parts that run longer than this are reported as `TIMEOUT`.
+    pub timeout: Option<Duration>,
+    /// Additionally run every day under the dhat heap profiler and attach the heap usage of each
+    /// part to its record. Profiling always spawns a build of the `dhat` profile.
+    pub memory: bool,
+}
+
+impl Default for RunOptions {
+    fn default() -> Self {
+        Self {
+            is_release: false,
+            is_timed: false,
+            spawn: false,
+            jobs: 1,
+            timeout: None,
+            memory: false,
+        }
+    }
+}
 
-    let mut need_space = false;
+/// Destination of a day's output: printed immediately, or buffered to be printed in day order.
+pub enum Sink {
+    Stdout,
+    Buffer(String),
+}
+
+impl Sink {
+    pub fn println(&mut self, line: impl Display) {
+        match self {
+            Sink::Stdout => println!("{line}"),
+            Sink::Buffer(buffer) => {
+                buffer.push_str(&line.to_string());
+                buffer.push('\n');
+            }
+        }
+    }
 
+    pub fn print(&mut self, text: impl Display) {
+        match self {
+            Sink::Stdout => print!("{text}"),
+            Sink::Buffer(buffer) => buffer.push_str(&text.to_string()),
+        }
+    }
+}
+
+/// The outcome of running a single day.
+pub struct DayRun {
+    pub puzzle: Puzzle,
+    /// Empty if the day is not solved (or could not be run).
+    pub records: Vec<PartRecord>,
+    pub output: Sink,
+    pub elapsed: Duration,
+}
+
+/// Set once a part that ran in-process timed out. Its thread can not be stopped and keeps running
+/// until the process exits, so all later days are spawned to keep their timings unaffected.
+static HAS_STRAY_PART: AtomicBool = AtomicBool::new(false);
+
+/// Runs the given days of `year`.
+pub fn run_multi(year: Year, days_to_run: &HashSet<Day>, options: RunOptions) -> Option<Timings> {
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
+    let days: Vec<Puzzle> = all_days()
         .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
+        .map(|day| Puzzle::new(year, day))
+        .collect();
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+    if options.jobs > 1 && !options.is_timed {
+        run_parallel(&days, options);
+        return None;
+    }
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+    let mut timings: Vec<Timing> = Vec::with_capacity(days.len());
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
-            }
-        });
+    for (i, puzzle) in days.into_iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+
+        let run = run_day(puzzle, options, Sink::Stdout);
+
+        if !run.records.is_empty() {
+            timings.push(child_commands::collect_timing(&run.records, puzzle.day));
+        }
+    }
 
-    if is_timed {
+    if options.is_timed {
         let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
         println!(
@@ -46,10 +130,231 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
     }
 }
 
+fn run_day(puzzle: Puzzle, options: RunOptions, mut output: Sink) -> DayRun {
+    let timer = Instant::now();
+
+    output.println(format!("{ANSI_BOLD}Day {puzzle}{ANSI_RESET}"));
+    output.println("------");
+
+    let is_spawned = options.spawn || HAS_STRAY_PART.load(Ordering::Relaxed);
+
+    let mut records = match registry::get(puzzle) {
+        Some(solution) if !is_spawned => run_in_process(solution, options, &mut output),
+        _ => child_commands::run_solution(puzzle, options, &mut output).unwrap_or_else(|e| {
+            let records = failed_records(puzzle, &e.to_string(), timer.elapsed());
+
+            for record in &records {
+                output.print(child_commands::format_record(record));
+            }
+
+            records
+        }),
+    };
+
+    if records.is_empty() {
+        output.println("Not solved.");
+    } else if options.memory {
+        attach_memory(puzzle, options, &mut records, &mut output);
+    }
+
+    DayRun {
+        puzzle,
+        records,
+        output,
+        elapsed: timer.elapsed(),
+    }
+}
+
+/// Records of a day whose binary could not be run: every part failed with `message`.
+fn failed_records(puzzle: Puzzle, message: &str, duration: Duration) -> Vec<PartRecord> {
+    let parts = registry::get(puzzle).map_or(&[1, 2][..], |solution| solution.parts);
+
+    parts
+        .iter()
+        .map(|part| PartRecord {
+            year: puzzle.year,
+            day: puzzle.day,
+            part: *part,
+            answer: None,
+            duration_nanos: duration.as_nanos() as f64,
+            stats: None,
+            failure: Some(Failure::Error(message.to_string())),
+            memory: None,
+        })
+        .collect()
+}
+
+/// Run a day under the heap profiler and attach the heap usage of each part to its record.
+fn attach_memory(
+    puzzle: Puzzle,
+    options: RunOptions,
+    records: &mut [PartRecord],
+    output: &mut Sink,
+) {
+    // the answers were already printed by the regular run.
+    let mut profile_output = Sink::Buffer(String::new());
+
+    let profiled =
+        match child_commands::profile_memory(puzzle, options.timeout, &mut profile_output) {
+            Ok(profiled) => profiled,
+            Err(e) => {
+                eprintln!("Failed to profile memory of day {puzzle}: {e}");
+                return;
+            }
+        };
+
+    for record in records {
+        record.memory = profiled
+            .iter()
+            .find(|p| p.part == record.part)
+            .and_then(|p| p.memory);
+
+        if let Some(memory) = &record.memory {
+            output.println(format!("{} memory: {memory}", part_label(record.part)));
+        }
+    }
+}
+
+/// Run days on `options.jobs` worker threads. Each day's output is buffered and printed in day
+/// order as soon as all previous days are done, followed by a summary table.
+fn run_parallel(days: &[Puzzle], options: RunOptions) {
+    let timer = Instant::now();
+
+    let runs = run_each(days, options, |i, run| {
+        if i > 0 {
+            println!();
+        }
+        if let Sink::Buffer(output) = &run.output {
+            print!("{output}");
+        }
+    });
+
+    print_summary(&runs, timer.elapsed(), options.jobs);
+}
+
+/// Run days on `options.jobs` worker threads with buffered output.
+/// `on_run` is called with the index and result of every day in day order, as soon as all previous
+/// days are done.
+pub fn run_each(
+    days: &[Puzzle],
+    options: RunOptions,
+    mut on_run: impl FnMut(usize, &DayRun),
+) -> Vec<DayRun> {
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    let mut runs: Vec<Option<DayRun>> = days.iter().map(|_| None).collect();
+
+    thread::scope(|scope| {
+        for _ in 0..options.jobs.clamp(1, days.len().max(1)) {
+            let sender = sender.clone();
+            let next = &next;
+
+            scope.spawn(move || loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(puzzle) = days.get(i) else {
+                    break;
+                };
+
+                let run = run_day(*puzzle, options, Sink::Buffer(String::new()));
+
+                if sender.send((i, run)).is_err() {
+                    break;
+                }
+            });
+        }
+
+        drop(sender);
+
+        let mut done = 0;
+
+        for (i, run) in receiver {
+            runs[i] = Some(run);
+
+            while let Some(Some(run)) = runs.get(done) {
+                on_run(done, run);
+                done += 1;
+            }
+        }
+    });
+
+    runs.into_iter().flatten().collect()
+}
+
+fn print_summary(runs: &[DayRun], wall_clock: Duration, jobs: usize) {
+    println!("\n{ANSI_BOLD}Summary{ANSI_RESET}");
+    println!("------");
+    println!("| Day | Part 1 | Part 2 | Time |");
+    println!("| :---: | :---: | :---: | :---: |");
+
+    for run in runs {
+        let part = |part: u8| {
+            run.records
+                .iter()
+                .find(|r| r.part == part)
+                .map_or("-".to_string(), |r| match (&r.answer, &r.failure) {
+                    (_, Some(failure)) => failure.to_string().replace('\n', " "),
+                    (Some(answer), None) if answer.contains('\n') => "▼".into(),
+                    (Some(answer), None) => answer.clone(),
+                    (None, None) => "✖".into(),
+                })
+        };
+
+        println!(
+            "| {} | {} | {} | {:.1?} |",
+            run.puzzle,
+            part(1),
+            part(2),
+            run.elapsed
+        );
+    }
+
+    let sequential: Duration = runs.iter().map(|r| r.elapsed).sum();
+
+    println!(
+        "\n{ANSI_BOLD}Total (Wall clock, {jobs} jobs):{ANSI_RESET} {ANSI_ITALIC}{:.2}ms{ANSI_RESET} (sum of days: {:.2}ms)",
+        wall_clock.as_secs_f64() * 1000.0,
+        sequential.as_secs_f64() * 1000.0
+    );
+}
+
+/// Run all parts of a solution linked into this binary, printing the same output as a child process would.
+fn run_in_process(solution: &Entry, options: RunOptions, output: &mut Sink) -> Vec<PartRecord> {
+    let input = match InputSource::Default.read(solution.puzzle) {
+        Ok(input) => input,
+        Err(e) => {
+            let message = format!("could not open input file: {e}");
+            let records = failed_records(solution.puzzle, &message, Duration::ZERO);
+
+            for record in &records {
+                output.print(child_commands::format_record(record));
+            }
+
+            return records;
+        }
+    };
+
+    let records = (solution.run)(&input, options.is_timed, options.timeout);
+
+    for record in &records {
+        output.print(child_commands::format_record(record));
+    }
+
+    if records.iter().any(|r| r.failure == Some(Failure::Timeout))
+        && !HAS_STRAY_PART.swap(true, Ordering::Relaxed)
+    {
+        output.println("Running the remaining days in their own processes.");
+    }
+
+    records
+}
+
 #[derive(Debug)]
 pub enum Error {
     BrokenPipe,
     IO(io::Error),
+    /// The child process did not exit successfully, e.g. because the solution failed to build.
+    Exit(ExitStatus),
 }
 
 impl From<std::io::Error> for Error {
@@ -58,48 +363,109 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::BrokenPipe => write!(f, "could not capture child process output."),
+            Error::IO(e) => write!(f, "{e}"),
+            Error::Exit(status) => match status.code() {
+                Some(code) => write!(f, "the solution binary exited with status {code}."),
+                None => write!(f, "the solution binary was terminated by a signal."),
+            },
+        }
+    }
 }
 
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as collecting the
+/// structured records they print with `--format json`.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::{Error, RunOptions, Sink};
+    use crate::template::record::{PartRecord, PARSE};
+    use crate::template::registry;
+    use crate::template::runner::{
+        format_duration, format_failure, format_memory, format_parse, format_result, part_label,
+    };
+    use crate::template::{Day, Puzzle};
     use std::{
         io::{BufRead, BufReader},
-        path::Path,
         process::{Command, Stdio},
         thread,
+        time::Duration,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
-        }
-
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
-
-        if is_release {
+    /// Run the solution bin for a given puzzle.
+    /// Part records are printed in the usual human-readable format, any other output is forwarded as is.
+    pub fn run_solution(
+        puzzle: Puzzle,
+        options: RunOptions,
+        output: &mut Sink,
+    ) -> Result<Vec<PartRecord>, Error> {
+        let bin_name = puzzle.to_string();
+        let mut args = vec!["run", "--quiet", "--bin", &bin_name];
+
+        if options.is_release {
             args.push("--release");
         }
 
-        if is_timed {
+        args.extend(["--", "--format", "json"]);
+
+        if options.is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
             args.push("--time");
         }
 
+        // the child enforces the timeout itself, so compile time does not count towards it.
+        let timeout = options.timeout.map(|t| format!("{t:?}"));
+        if let Some(timeout) = &timeout {
+            args.extend(["--timeout", timeout]);
+        }
+
+        run_bin(puzzle, &args, output)
+    }
+
+    /// Run the solution bin for a given puzzle under the dhat heap profiler.
+    /// The records carry the heap usage of each part.
+    pub fn profile_memory(
+        puzzle: Puzzle,
+        timeout: Option<Duration>,
+        output: &mut Sink,
+    ) -> Result<Vec<PartRecord>, Error> {
+        let bin_name = puzzle.to_string();
+        let mut args = vec![
+            "run",
+            "--quiet",
+            "--bin",
+            &bin_name,
+            "--profile",
+            "dhat",
+            "--features",
+            "dhat-heap",
+            "--",
+            "--format",
+            "json",
+        ];
+
+        let timeout = timeout.map(|t| format!("{t:?}"));
+        if let Some(timeout) = &timeout {
+            args.extend(["--timeout", timeout]);
+        }
+
+        run_bin(puzzle, &args, output)
+    }
+
+    /// Invoke `cargo` with `args` to run the bin of `puzzle`.
+    fn run_bin(puzzle: Puzzle, args: &[&str], output: &mut Sink) -> Result<Vec<PartRecord>, Error> {
+        // skip command invocation for days that have not been implemented yet.
+        if registry::get(puzzle).is_none() {
+            return Ok(vec![]);
+        }
+
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // forward output to stdout/stderr while grabbing part records.
 
         let mut cmd = Command::new("cargo")
-            .args(&args)
+            .args(args)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .spawn()?;
@@ -107,87 +473,120 @@ pub mod child_commands {
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
+        let mut records = vec![];
 
+        // stderr is only forwarded, invalid UTF-8 is replaced instead of failing the run.
         let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
+            for line in stderr.split(b'\n').map_while(Result::ok) {
+                eprintln!("{}", String::from_utf8_lossy(&line));
+            }
         });
 
         for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+            let line = match line {
+                Ok(line) => line,
+                Err(e) => {
+                    // the child would block on a full pipe once its output is no longer read.
+                    let _ = cmd.kill();
+                    let _ = cmd.wait();
+                    return Err(e.into());
+                }
+            };
+
+            match PartRecord::parse_line(&line) {
+                Some(record) => {
+                    output.print(format_record(&record));
+                    records.push(record);
+                }
+                None => output.println(line),
+            }
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        thread.join().map_err(|_| Error::BrokenPipe)?;
+        let status = cmd.wait()?;
+
+        if !status.success() {
+            return Err(Error::Exit(status));
+        }
 
-        Ok(output)
+        Ok(records)
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
+    /// Formats a record like the runner of a solution binary prints it.
+    pub(super) fn format_record(record: &PartRecord) -> String {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let duration = Duration::from_nanos(record.duration_nanos.round() as u64);
+        let part = part_label(record.part);
+
+        if let Some(failure) = &record.failure {
+            return format_failure(failure, &part, &duration);
+        }
+
+        let mut duration_str = format_duration(&duration, record.stats.as_ref());
+
+        if let Some(memory) = &record.memory {
+            duration_str.push_str(&format_memory(memory));
+        }
+
+        if record.is_parse() {
+            return format_parse(&duration_str);
+        }
+
+        format_result(&record.answer, &part, &duration_str)
+    }
+
+    /// Collects the timings of all benched, solved parts and of the benched parse phase.
+    pub fn collect_timing(records: &[PartRecord], day: Day) -> super::Timing {
         let mut timings = super::Timing {
             day,
             part_1: None,
             part_2: None,
+            parse: None,
             total_nanos: 0_f64,
+            parse_stats: None,
+            part_1_stats: None,
+            part_2_stats: None,
+            part_1_memory: None,
+            part_2_memory: None,
         };
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+        for record in records {
+            let is_complete = record.is_solved() || (record.is_parse() && record.failure.is_none());
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
+            if record.day != day || !is_complete || record.stats.is_none() {
+                continue;
+            }
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let timing_str = format!(
+                "{:.1?}",
+                Duration::from_nanos(record.duration_nanos.round() as u64)
+            );
+
+            match record.part {
+                PARSE => {
+                    timings.parse = Some(timing_str);
+                    timings.parse_stats.clone_from(&record.stats);
+                }
+                1 => {
+                    timings.part_1 = Some(timing_str);
+                    timings.part_1_stats.clone_from(&record.stats);
+                    timings.part_1_memory = record.memory;
                 }
+                2 => {
+                    timings.part_2 = Some(timing_str);
+                    timings.part_2_stats.clone_from(&record.stats);
+                    timings.part_2_memory = record.memory;
+                }
+                _ => continue,
+            }
 
-                timings.total_nanos += nanos;
-            });
+            timings.total_nanos += record.duration_nanos;
+        }
 
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
-    }
-
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
     #[cfg(feature = "test_lib")]
     macro_rules! assert_approx_eq {
@@ -204,47 +603,78 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::collect_timing;
+        use super::PARSE;
 
         use crate::day;
+        use crate::template::record::PartRecord;
+        use crate::template::stats::Stats;
+        use crate::template::{Day, Year};
+
+        fn stats(mean: f64) -> Stats {
+            Stats {
+                mean,
+                median: mean,
+                min: mean,
+                max: mean,
+                std_dev: 0.0,
+                p95: mean,
+                p99: mean,
+                samples: 10,
+                warmup: 1,
+                mild_outliers: 0,
+                severe_outliers: 0,
+            }
+        }
+
+        fn record(day: Day, part: u8, answer: Option<&str>, nanos: f64, timed: bool) -> PartRecord {
+            PartRecord {
+                year: Year::primary(),
+                day,
+                part,
+                answer: answer.map(Into::into),
+                duration_nanos: nanos,
+                stats: timed.then(|| stats(nanos)),
+                failure: None,
+                memory: None,
+            }
+        }
 
         #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
+        fn collects_execution_times() {
+            let res = collect_timing(
                 &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
+                    record(day!(1), 1, Some("0"), 74.13, true),
+                    record(day!(1), 2, Some("10"), 74_130_000.0, true),
                 ],
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_eq!(res.part_1.unwrap(), "74.0ns");
+            assert_eq!(res.part_2.unwrap(), "74.1ms");
+            assert_eq!(res.part_1_stats.unwrap().samples, 10);
         }
 
         #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
+        fn handles_patterns_in_answers() {
+            let res = collect_timing(
                 &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
+                    record(day!(1), 1, Some("@ @ ( ) ms (2s @ 5 samples)"), 2e9, true),
+                    record(day!(1), 2, Some("#..#\n.##.\n#..#"), 1e8, true),
                 ],
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_eq!(res.part_1.unwrap(), "2.0s");
+            assert_eq!(res.part_2.unwrap(), "100.0ms");
         }
 
         #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
+        fn handles_missing_parts() {
+            let res = collect_timing(
                 &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
+                    record(day!(1), 1, None, 0.0, false),
+                    record(day!(1), 2, None, 0.0, false),
                 ],
                 day!(1),
             );
@@ -252,5 +682,27 @@ pub mod child_commands {
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
+
+        #[test]
+        fn collects_parse_times() {
+            let res = collect_timing(
+                &[
+                    record(day!(1), PARSE, None, 2_000.0, true),
+                    record(day!(1), 1, Some("0"), 1_000.0, true),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 3_000_f64);
+            assert_eq!(res.parse.unwrap(), "2.0µs");
+            assert_eq!(res.part_1.unwrap(), "1.0µs");
+            assert_eq!(res.parse_stats.unwrap().mean, 2_000.0);
+        }
+
+        #[test]
+        fn ignores_untimed_parts() {
+            let res = collect_timing(&[record(day!(1), 1, Some("1"), 500.0, false)], day!(1));
+            assert_approx_eq!(res.total_nanos, 0_f64);
+            assert_eq!(res.part_1.is_none(), true);
+        }
     }
 }
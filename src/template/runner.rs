@@ -1,70 +1,496 @@
 /// Encapsulates code that interacts with solution functions.
+use std::any::Any;
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
-use std::time::{Duration, Instant};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use std::{cmp, env, process};
 
+use crate::template::answers::{self, get_path_for_answers, verdict, Verdict};
+use crate::template::aoc_client::{Client, ClientError, SubmitOutcome};
+use crate::template::inputs::{self, InputSelection, InputSource};
+#[cfg(feature = "dhat-heap")]
+use crate::template::memory::HeapProfiler;
+use crate::template::memory::MemoryStats;
+use crate::template::record::{Failure, PartRecord, PARSE};
+use crate::template::stats::{parse_duration, Stats};
+use crate::template::submissions::{Ledger, Submission};
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{Puzzle, Solution, ANSI_ITALIC, ANSI_RESET};
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
-    let part_str = format!("Part {part}");
+/// Runs the solution `S` of `puzzle` on the input selected on the command line and prints the
+/// result of each part. This is the `main` function of every solution binary, see `solution!`.
+pub fn run<S: Solution + 'static>(puzzle: Puzzle) {
+    let Some(input) = selected_input(puzzle) else {
+        return cross_check::<S>(puzzle);
+    };
+
+    let is_timed = env::args().any(|x| x == "--time");
+
+    let input = match parse::<S>(input, is_timed, timeout_arg(), true) {
+        Ok(run) => {
+            print_parse(&run, puzzle);
+            run.result
+        }
+        Err((failure, duration)) => {
+            // the parts need the parsed input, they are not run.
+            print_failure(&failure, puzzle, PARSE, duration);
+            return;
+        }
+    };
+
+    for part in S::PARTS {
+        match part {
+            1 => run_part(S::part_one, Arc::clone(&input), puzzle, 1),
+            2 => run_part(S::part_two, Arc::clone(&input), puzzle, 2),
+            _ => {}
+        }
+    }
+}
+
+fn run_part<I: ?Sized + Send + Sync + 'static, T: Display + Send + 'static>(
+    func: impl Fn(&I) -> Option<T> + Send + 'static,
+    input: Arc<I>,
+    puzzle: Puzzle,
+    part: u8,
+) {
+    let part_str = part_label(part);
+    let is_json = is_json_output();
+    let is_timed = env::args().any(|x| x == "--time");
+    let timeout = timeout_arg();
+
+    let hook = {
+        let part_str = part_str.clone();
+
+        move |result: &Option<T>| {
+            if !is_json {
+                print_result(result, &part_str, "");
+
+                if is_timed {
+                    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+                    let _ = stdout().flush();
+                }
+            }
+        }
+    };
+
+    let run = match run_isolated(func, input, is_timed, timeout, hook) {
+        Ok(run) => run,
+        Err((failure, duration)) => {
+            print_failure(&failure, puzzle, part, duration);
+            return;
+        }
+    };
+
+    if is_json {
+        println!("{}", to_record(&run, puzzle, part).to_json_line());
+    } else {
+        let mut duration_str = format_duration(&run.duration, run.stats.as_ref());
+
+        if let Some(memory) = &run.memory {
+            duration_str.push_str(&format_memory(memory));
+        }
+
+        print_result(&run.result, &part_str, &duration_str);
+    }
+
+    if let Some(result) = run.result {
+        submit_result(result, puzzle, part);
+    }
+}
+
+fn print_failure(failure: &Failure, puzzle: Puzzle, part: u8, duration: Duration) {
+    if is_json_output() {
+        let record = to_failed_record(failure.clone(), puzzle, part, duration);
+        println!("{}", record.to_json_line());
+    } else {
+        print!(
+            "\r{}",
+            format_failure(failure, &part_label(part), &duration)
+        );
+    }
+}
+
+fn print_parse<T>(run: &TimedRun<T>, puzzle: Puzzle) {
+    if is_json_output() {
+        println!("{}", to_parse_record(run, puzzle).to_json_line());
+    } else {
+        let mut duration_str = format_duration(&run.duration, run.stats.as_ref());
+
+        if let Some(memory) = &run.memory {
+            duration_str.push_str(&format_memory(memory));
+        }
+
+        print!("\r{}", format_parse(&duration_str));
+    }
+}
+
+/// Parses the input of the solution `S` once, to be shared by its parts.
+/// Parsing is benched like a part if `is_timed` is set, and shows progress if `is_shown`.
+/// Panics and timeouts are caught like those of a part, see [`run_isolated`]. Errors returned by
+/// the solution are reported as [`Failure::Error`].
+fn parse<S: Solution + 'static>(
+    input: String,
+    is_timed: bool,
+    timeout: Option<Duration>,
+    is_shown: bool,
+) -> Result<TimedRun<Arc<S::Input>>, (Failure, Duration)> {
+    let is_shown = is_shown && is_timed && !is_json_output();
+
+    let run = run_isolated(
+        |input: &String| S::parse(input).map(Arc::new),
+        Arc::new(input),
+        is_timed,
+        timeout,
+        move |_| {
+            if is_shown {
+                print!("Parse: > {ANSI_ITALIC}benching{ANSI_RESET}");
+                let _ = stdout().flush();
+            }
+        },
+    )?;
+
+    match run.result {
+        Ok(input) => Ok(TimedRun {
+            result: input,
+            duration: run.duration,
+            stats: run.stats,
+            memory: run.memory,
+        }),
+        Err(e) => Err((Failure::Error(e.to_string()), run.duration)),
+    }
+}
+
+/// Reads the input selected with `--input` or `--input-set`, see [`inputs`].
+/// Returns `None` if the binary was asked to cross-check all inputs.
+pub fn selected_input(puzzle: Puzzle) -> Option<String> {
+    let source = match inputs::from_args() {
+        Ok(InputSelection::Single(source)) => source,
+        Ok(InputSelection::CrossCheck) => return None,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    match source.read(puzzle) {
+        Ok(input) => Some(input),
+        Err(e) => {
+            eprintln!("Failed to read {source}: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Runs every part on the default input and on every named input, and compares the results with
+/// the answers recorded for each input. Exits with an error if any input disagrees.
+pub fn cross_check<S: Solution + 'static>(puzzle: Puzzle) {
+    let timeout = timeout_arg();
+
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+    let mut disagreeing = vec![];
+
+    for source in inputs::all_inputs(puzzle) {
+        let input = match source.read(puzzle) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("Failed to read {source}: {e}");
+                continue;
+            }
+        };
+
+        let answers = source.answers(puzzle).unwrap_or_else(|e| {
+            eprintln!("Failed to read answers of {source}: {e}");
+            answers::Answers::default()
+        });
+
+        let mut agrees = true;
+
+        let records = record::<S>(&input, puzzle, false, timeout);
+
+        for record in records.iter().filter(|record| !record.is_parse()) {
+            let part = record.part;
+            let expected = answers.get(part);
+            let actual = record.answer.as_deref();
+
+            let got = match (actual, &record.failure) {
+                (Some(actual), _) => format!("{actual:?}"),
+                (None, Some(failure)) => failure.to_string(),
+                (None, None) => "✖".into(),
+            };
+
+            let line = match verdict(expected, actual) {
+                Verdict::Pass => {
+                    passed += 1;
+                    format!("PASS ({got})")
+                }
+                Verdict::Fail => {
+                    failed += 1;
+                    agrees = false;
+                    format!(
+                        "FAIL (expected {:?}, got {got})",
+                        expected.unwrap_or_default()
+                    )
+                }
+                Verdict::Missing => {
+                    missing += 1;
+                    format!("MISSING (got {got})")
+                }
+            };
+
+            println!("{source} / Part {part}: {line}");
+        }
+
+        if !agrees {
+            disagreeing.push(source.to_string());
+        }
+    }
+
+    println!(
+        "\n{ANSI_BOLD}Cross-checked:{ANSI_RESET} {passed} passed, {failed} failed, {missing} missing."
+    );
+
+    if !disagreeing.is_empty() {
+        println!("Disagreeing: {}", disagreeing.join(", "));
+        process::exit(1);
+    }
+}
+
+/// Parses `input` and runs the parts of the solution `S` on it without printing anything.
+/// The parse phase is recorded ahead of the parts, see [`crate::template::record`]. If the input
+/// can not be parsed, only the failed parse phase is recorded.
+/// Used to run solutions in-process, see [`crate::template::registry`].
+pub fn record<S: Solution + 'static>(
+    input: &str,
+    puzzle: Puzzle,
+    is_timed: bool,
+    timeout: Option<Duration>,
+) -> Vec<PartRecord> {
+    let mut records = vec![];
+
+    let input = match parse::<S>(input.to_string(), is_timed, timeout, false) {
+        Ok(run) => {
+            records.push(to_parse_record(&run, puzzle));
+            run.result
+        }
+        Err((failure, duration)) => {
+            records.push(to_failed_record(failure, puzzle, PARSE, duration));
+            return records;
+        }
+    };
+
+    records.extend(S::PARTS.iter().filter_map(|part| match part {
+        1 => Some(record_part(
+            S::part_one,
+            Arc::clone(&input),
+            puzzle,
+            1,
+            is_timed,
+            timeout,
+        )),
+        2 => Some(record_part(
+            S::part_two,
+            Arc::clone(&input),
+            puzzle,
+            2,
+            is_timed,
+            timeout,
+        )),
+        _ => None,
+    }));
+
+    records
+}
+
+/// Run a solution part without printing anything and return its record.
+fn record_part<I: ?Sized + Send + Sync + 'static, T: Display + Send + 'static>(
+    func: impl Fn(&I) -> Option<T> + Send + 'static,
+    input: Arc<I>,
+    puzzle: Puzzle,
+    part: u8,
+    is_timed: bool,
+    timeout: Option<Duration>,
+) -> PartRecord {
+    match run_isolated(func, input, is_timed, timeout, |_| {}) {
+        Ok(run) => to_record(&run, puzzle, part),
+        Err((failure, duration)) => to_failed_record(failure, puzzle, part, duration),
+    }
+}
+
+fn to_record<T: Display>(run: &TimedRun<Option<T>>, puzzle: Puzzle, part: u8) -> PartRecord {
+    PartRecord {
+        year: puzzle.year,
+        day: puzzle.day,
+        part,
+        answer: run.result.as_ref().map(ToString::to_string),
+        duration_nanos: run.duration.as_nanos() as f64,
+        stats: run.stats.clone(),
+        failure: None,
+        memory: run.memory,
+    }
+}
+
+fn to_parse_record<T>(run: &TimedRun<T>, puzzle: Puzzle) -> PartRecord {
+    PartRecord {
+        year: puzzle.year,
+        day: puzzle.day,
+        part: PARSE,
+        answer: None,
+        duration_nanos: run.duration.as_nanos() as f64,
+        stats: run.stats.clone(),
+        failure: None,
+        memory: run.memory,
+    }
+}
+
+fn to_failed_record(failure: Failure, puzzle: Puzzle, part: u8, duration: Duration) -> PartRecord {
+    PartRecord {
+        year: puzzle.year,
+        day: puzzle.day,
+        part,
+        answer: None,
+        duration_nanos: duration.as_nanos() as f64,
+        stats: None,
+        failure: Some(failure),
+        memory: None,
+    }
+}
+
+/// The outcome of [`run_timed`].
+struct TimedRun<T> {
+    result: T,
+    /// Mean duration when benched, the duration of the single run otherwise.
+    duration: Duration,
+    stats: Option<Stats>,
+    /// Heap usage of the first execution, only measured when built with `dhat-heap`.
+    memory: Option<MemoryStats>,
+}
+
+/// Run a solution part on its own thread, see [`run_timed`].
+///
+/// Panics are caught and reported as [`Failure::Panic`]. If the first execution of the part does
+/// not finish within `timeout`, [`Failure::Timeout`] is returned. Benching is not subject to the
+/// timeout. A timed out part can not be stopped: its thread keeps running until the process exits.
+/// On failure, the time until the part was given up is returned alongside.
+fn run_isolated<I: ?Sized + Send + Sync + 'static, T: Send + 'static>(
+    func: impl Fn(&I) -> T + Send + 'static,
+    input: Arc<I>,
+    is_timed: bool,
+    timeout: Option<Duration>,
+    hook: impl Fn(&T) + Send + 'static,
+) -> Result<TimedRun<T>, (Failure, Duration)> {
+    // `None` signals that the first execution finished, the result follows once benching is done.
+    let (sender, receiver) = mpsc::channel::<Option<thread::Result<TimedRun<T>>>>();
+    let timer = Instant::now();
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    thread::spawn(move || {
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            run_timed(&func, &*input, is_timed, |result| {
+                let _ = sender.send(None);
+                hook(result);
+            })
+        }));
+        let _ = sender.send(Some(result));
+    });
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    let mut message = match timeout {
+        Some(timeout) => receiver.recv_timeout(timeout),
+        None => receiver.recv().map_err(RecvTimeoutError::from),
+    };
+
+    // the first execution finished in time, wait for the bench.
+    if let Ok(None) = message {
+        message = receiver.recv().map_err(RecvTimeoutError::from);
+    }
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    match message {
+        Ok(Some(Ok(run))) => Ok(run),
+        Ok(Some(Err(payload))) => Err((Failure::Panic(panic_message(&*payload)), timer.elapsed())),
+        Ok(None) | Err(RecvTimeoutError::Disconnected) => Err((
+            Failure::Panic("the part exited unexpectedly.".into()),
+            timer.elapsed(),
+        )),
+        Err(RecvTimeoutError::Timeout) => Err((Failure::Timeout, timer.elapsed())),
     }
 }
 
-/// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
-///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+/// Extracts the message passed to `panic!`.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "Box<dyn Any>".to_string()
+    }
+}
+
+/// Run a solution part. The behavior differs depending on whether the part is timed:
+///  1. if not, the function is executed once.
+///  2. if timed, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///     The benched duration is the mean of all samples, the full statistics are returned alongside.
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
+    is_timed: bool,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> TimedRun<T> {
     let timer = Instant::now();
-    let result = {
+    let (result, memory) = {
         let input = input.clone();
 
+        // machine-readable runs only need the summary, don't write `dhat-heap.json` for them.
+        // a part that timed out still holds its profiler, later parts are not measured then.
+        #[cfg(feature = "dhat-heap")]
+        let profiler = HeapProfiler::start(!is_json_output());
+
+        let result = func(input);
+
         #[cfg(feature = "dhat-heap")]
-        let _profiler = dhat::Profiler::new_heap();
+        let memory = profiler.map(|_profiler| MemoryStats::current());
+        #[cfg(not(feature = "dhat-heap"))]
+        let memory = None;
 
-        func(input)
+        (result, memory)
     };
     let base_time = timer.elapsed();
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    if is_timed {
+        let stats = bench(func, input, &base_time);
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let mean = Duration::from_nanos(stats.mean.round() as u64);
+        TimedRun {
+            result,
+            duration: mean,
+            stats: Some(stats),
+            memory,
+        }
     } else {
-        (base_time, 1)
-    };
-
-    (result, run.0, run.1)
+        TimedRun {
+            result,
+            duration: base_time,
+            stats: None,
+            memory,
+        }
+    }
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+/// Bench a function. A tenth of the iterations (at least one) is run as warm-up and discarded.
+fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> Stats {
+    let bench_iterations =
+        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+    let warmup_iterations = cmp::max(bench_iterations / 10, 1);
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+    for _ in 0..warmup_iterations {
+        black_box(func(black_box(input.clone())));
+    }
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -76,72 +502,120 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    #[allow(clippy::cast_possible_truncation)]
+    Stats::from_samples(&timers, warmup_iterations as usize).unwrap()
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+/// Whether the binary was invoked with `--format json`, see [`crate::template::record`].
+fn is_json_output() -> bool {
+    let args: Vec<String> = env::args().collect();
+    args.windows(2)
+        .any(|w| w[0] == "--format" && w[1] == "json")
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
-    } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+/// The timeout the binary was invoked with, e.g. `--timeout 10s`.
+fn timeout_arg() -> Option<Duration> {
+    let args: Vec<String> = env::args().collect();
+    let value = args.windows(2).find(|w| w[0] == "--timeout")?.get(1)?;
+
+    match parse_timeout(value) {
+        Ok(timeout) => Some(timeout),
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+/// Parses a timeout such as `10s`, `1.5s` or `500ms`. Plain numbers are interpreted as seconds.
+pub fn parse_timeout(s: &str) -> Result<Duration, String> {
+    parse_duration(s)
+        .filter(|nanos| nanos.is_finite() && *nanos > 0.0)
+        .map(|nanos| Duration::from_secs_f64(nanos / 1e9))
+        .ok_or_else(|| format!("invalid timeout: {s:?}. Expected e.g. `10s` or `500ms`."))
+}
+
+pub(crate) fn format_duration(duration: &Duration, stats: Option<&Stats>) -> String {
+    match stats {
+        Some(stats) => format!(" ({duration:.1?} @ {} samples) {stats}", stats.samples),
+        None => format!(" ({duration:.1?})"),
+    }
+}
+
+pub(crate) fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
+    if !is_intermediate_result {
+        print!("\r{}", format_result(result, part, duration_str));
+        return;
+    }
+
     match result {
         Some(result) => {
             if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                    println!("{result}");
-                }
+                print!("{part}: ▼ ");
             } else {
-                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                }
+                print!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}");
             }
         }
-        None => {
-            if is_intermediate_result {
-                print!("{part}: ✖");
+        None => print!("{part}: ✖"),
+    }
+}
+
+/// Formats the final result line(s) of a part, including a trailing newline.
+pub(crate) fn format_result<T: Display>(
+    result: &Option<T>,
+    part: &str,
+    duration_str: &str,
+) -> String {
+    match result {
+        Some(result) => {
+            let result = result.to_string();
+            if result.contains('\n') {
+                format!("{part}: ▼ {duration_str}\n{result}\n")
             } else {
-                print!("\r");
-                println!("{part}: ✖             ");
+                format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}\n")
             }
         }
+        None => format!("{part}: ✖             \n"),
+    }
+}
+
+/// Formats the line of the parse phase, including a trailing newline.
+pub(crate) fn format_parse(duration_str: &str) -> String {
+    format!("Parse:{duration_str}\n")
+}
+
+/// Label of a part in the output, `Parse` for the parse phase.
+pub(crate) fn part_label(part: u8) -> String {
+    if part == PARSE {
+        "Parse".into()
+    } else {
+        format!("Part {part}")
     }
 }
 
+/// Formats heap usage to be appended to the duration of a part.
+pub(crate) fn format_memory(memory: &MemoryStats) -> String {
+    format!(" [{memory}]")
+}
+
+/// Formats the result line of a part that did not run to completion, including a trailing newline.
+pub(crate) fn format_failure(failure: &Failure, part: &str, duration: &Duration) -> String {
+    format!("{part}: {ANSI_BOLD}{failure}{ANSI_RESET} (after {duration:.1?})\n")
+}
+
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+///  2. a session cookie is configured, see [`crate::template::aoc_client`].
+///
+/// Answers that the ledger of previous submissions rules out are not submitted, see
+/// [`crate::template::submissions`]. Accepted answers are recorded in `data/answers`, see [`answers`].
 fn submit_result<T: Display>(
     result: T,
-    day: Day,
+    puzzle: Puzzle,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+) -> Option<Result<SubmitOutcome, ClientError>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -164,11 +638,201 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+    // answers of other inputs belong to other accounts.
+    if inputs::from_args() != Ok(InputSelection::Single(InputSource::Default)) {
+        eprintln!("Not submitting: only answers for the default input are submitted.");
+        return None;
+    }
+
+    let client = match Client::from_env() {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("Failed to submit result: {e}");
+            process::exit(1);
+        }
+    };
+
+    let answer = result.to_string();
+
+    let ledger = Ledger::read(puzzle).unwrap_or_else(|e| {
+        eprintln!("Failed to read submissions: {e}");
         process::exit(1);
+    });
+
+    let now = || {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs())
+    };
+
+    if let Err(refusal) = ledger.check(part, &answer, now()) {
+        eprintln!("Not submitting {answer:?}: {refusal}");
+        return None;
+    }
+
+    println!("Submitting result...");
+    let outcome = client.submit(puzzle, part, &answer);
+
+    match &outcome {
+        Ok(outcome) => {
+            println!("{outcome}");
+
+            let submission = Submission {
+                part,
+                answer: answer.clone(),
+                outcome: *outcome,
+                timestamp: now(),
+            };
+
+            if let Err(e) = Ledger::append(puzzle, &submission) {
+                eprintln!("Failed to record submission: {e}");
+            }
+        }
+        Err(e) => eprintln!("Failed to submit result: {e}"),
+    }
+
+    if let Ok(SubmitOutcome::Correct) = outcome {
+        match answers::record(puzzle, part, &answer) {
+            Ok(()) => println!("Recorded answer in \"{}\".", get_path_for_answers(puzzle)),
+            Err(e) => eprintln!("Failed to record answer: {e}"),
+        }
+    }
+
+    Some(outcome)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+
+    use super::{parse_timeout, record, record_part};
+    use crate::day;
+    use crate::template::record::{Failure, PARSE};
+    use crate::template::{ParseResult, Puzzle, Solution, Year};
+
+    const PUZZLE: Puzzle = Puzzle::new(Year::primary(), day!(1));
+
+    struct Sum;
+
+    impl Solution for Sum {
+        type Input = Vec<u32>;
+        type PartOne = u32;
+        type PartTwo = u32;
+
+        fn parse(input: &str) -> ParseResult<Self::Input> {
+            Ok(input.lines().map(str::parse).collect::<Result<_, _>>()?)
+        }
+
+        fn part_one(input: &Self::Input) -> Option<u32> {
+            Some(input.iter().sum())
+        }
+
+        fn part_two(input: &Self::Input) -> Option<u32> {
+            input.iter().max().copied()
+        }
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    #[test]
+    fn records_parsed_solutions() {
+        let records = record::<Sum>("1\n5\n2", PUZZLE, false, None);
+        let answers: Vec<_> = records.iter().map(|r| r.answer.as_deref()).collect();
+        assert_eq!(answers, [None, Some("8"), Some("5")]);
+        assert_eq!(records[0].part, PARSE);
+    }
+
+    #[test]
+    fn benches_parsing_once() {
+        let records = record::<Sum>("1\n5\n2", PUZZLE, true, None);
+        assert!(records.iter().all(|r| r.stats.is_some()));
+        assert_eq!(records.iter().filter(|r| r.is_parse()).count(), 1);
+    }
+
+    #[test]
+    fn skips_parts_if_parsing_fails() {
+        let records = record::<Sum>("1\nx", PUZZLE, false, None);
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].part, PARSE);
+        assert_eq!(
+            records[0].failure,
+            Some(Failure::Error("invalid digit found in string".into()))
+        );
+    }
+
+    #[test]
+    fn records_answers() {
+        let record = record_part(
+            |input: &str| Some(input.len()),
+            Arc::from("abc"),
+            PUZZLE,
+            1,
+            false,
+            None,
+        );
+        assert_eq!(record.answer.as_deref(), Some("3"));
+        assert_eq!(record.failure, None);
+    }
+
+    #[test]
+    fn captures_panics() {
+        let record = record_part(
+            |input: &str| -> Option<u32> { panic!("unexpected input: {input}") },
+            Arc::from("abc"),
+            PUZZLE,
+            2,
+            true,
+            None,
+        );
+        assert_eq!(record.answer, None);
+        assert_eq!(
+            record.failure,
+            Some(Failure::Panic("unexpected input: abc".into()))
+        );
+    }
+
+    #[test]
+    fn times_out_hanging_parts() {
+        let record = record_part(
+            |_: &str| -> Option<u32> {
+                thread::sleep(Duration::from_secs(60));
+                None
+            },
+            Arc::from(""),
+            PUZZLE,
+            1,
+            false,
+            Some(Duration::from_millis(50)),
+        );
+        assert_eq!(record.failure, Some(Failure::Timeout));
+        assert!(record.duration_nanos >= 5e7);
+    }
+
+    #[test]
+    fn does_not_time_out_benching() {
+        let record = record_part(
+            |_: &str| {
+                thread::sleep(Duration::from_millis(5));
+                Some(1)
+            },
+            Arc::from(""),
+            PUZZLE,
+            1,
+            true,
+            Some(Duration::from_millis(100)),
+        );
+        assert_eq!(record.answer.as_deref(), Some("1"));
+        assert!(record.stats.is_some());
+    }
+
+    #[test]
+    fn parses_timeouts() {
+        assert_eq!(parse_timeout("10"), Ok(Duration::from_secs(10)));
+        assert_eq!(parse_timeout("1.5s"), Ok(Duration::from_millis(1500)));
+        assert_eq!(parse_timeout("500ms"), Ok(Duration::from_millis(500)));
+        assert!(parse_timeout("0").is_err());
+        assert!(parse_timeout("soon").is_err());
+    }
 }
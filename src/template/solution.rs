@@ -0,0 +1,55 @@
+use std::error::Error;
+use std::fmt::Display;
+
+/// The result of [`Solution::parse`]. Any error can be returned, its message is reported as the
+/// failure of the parse phase.
+pub type ParseResult<T> = Result<T, Box<dyn Error + Send + Sync>>;
+
+/// A day's solution: the input is parsed once, then both parts are solved on the parsed input.
+///
+/// Parsing is shared by the parts and runs separately from them, so the time of a part does not
+/// include parsing the input; runners report the time of parsing on its own. Solutions are
+/// registered with the `solution!` macro, which also defines the `main` function of the day's
+/// binary:
+///
+/// ```
+/// use advent_of_code::template::{ParseResult, Solution};
+///
+/// advent_of_code::solution!(1, Day01);
+///
+/// pub struct Day01;
+///
+/// impl Solution for Day01 {
+///     type Input = Vec<u32>;
+///     type PartOne = u32;
+///     type PartTwo = u32;
+///
+///     fn parse(input: &str) -> ParseResult<Self::Input> {
+///         Ok(input.lines().map(str::parse).collect::<Result<_, _>>()?)
+///     }
+///
+///     fn part_one(input: &Self::Input) -> Option<u32> {
+///         Some(input.iter().sum())
+///     }
+///
+///     fn part_two(input: &Self::Input) -> Option<u32> {
+///         input.iter().max().copied()
+///     }
+/// }
+/// ```
+pub trait Solution {
+    /// The parsed input, shared by both parts.
+    type Input: Send + Sync + 'static;
+    type PartOne: Display + Send + 'static;
+    type PartTwo: Display + Send + 'static;
+
+    /// The parts this solution implements, e.g. `&[1]` while part two is not solved yet.
+    const PARTS: &'static [u8] = &[1, 2];
+
+    /// Parses the input. If it can not be parsed, the parts are not run.
+    fn parse(input: &str) -> ParseResult<Self::Input>;
+
+    fn part_one(input: &Self::Input) -> Option<Self::PartOne>;
+
+    fn part_two(input: &Self::Input) -> Option<Self::PartTwo>;
+}
@@ -0,0 +1,178 @@
+/// Summary statistics for benchmark samples.
+///
+/// Outliers are classified with Tukey's fences: samples further than 1.5 × IQR outside of the
+/// interquartile range are mild outliers, samples further than 3 × IQR are severe outliers.
+use std::fmt::Display;
+use std::time::Duration;
+
+/// Summary of a benchmark run. All durations are stored as nanoseconds.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stats {
+    pub mean: f64,
+    pub median: f64,
+    pub min: f64,
+    pub max: f64,
+    pub std_dev: f64,
+    pub p95: f64,
+    pub p99: f64,
+    pub samples: usize,
+    pub warmup: usize,
+    pub mild_outliers: usize,
+    pub severe_outliers: usize,
+}
+
+impl Stats {
+    /// Computes statistics for a set of measured samples. Returns `None` if `samples` is empty.
+    pub fn from_samples(samples: &[Duration], warmup: usize) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut nanos: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+        nanos.sort_unstable_by(f64::total_cmp);
+
+        let n = nanos.len() as f64;
+        let mean = nanos.iter().sum::<f64>() / n;
+
+        let std_dev = if nanos.len() > 1 {
+            (nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt()
+        } else {
+            0.0
+        };
+
+        let q1 = quantile(&nanos, 0.25);
+        let q3 = quantile(&nanos, 0.75);
+        let iqr = q3 - q1;
+
+        let outside = |factor: f64| {
+            nanos
+                .iter()
+                .filter(|x| **x < q1 - factor * iqr || **x > q3 + factor * iqr)
+                .count()
+        };
+
+        let severe_outliers = outside(3.0);
+
+        Some(Stats {
+            mean,
+            median: quantile(&nanos, 0.5),
+            min: nanos[0],
+            max: nanos[nanos.len() - 1],
+            std_dev,
+            p95: quantile(&nanos, 0.95),
+            p99: quantile(&nanos, 0.99),
+            samples: nanos.len(),
+            warmup,
+            mild_outliers: outside(1.5) - severe_outliers,
+            severe_outliers,
+        })
+    }
+
+    pub fn outliers(&self) -> usize {
+        self.mild_outliers + self.severe_outliers
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "[median {:.1?}, min {:.1?}, max {:.1?}, σ {:.1?}, p95 {:.1?}, p99 {:.1?}, warm-up {}, outliers {} mild / {} severe]",
+            to_duration(self.median),
+            to_duration(self.min),
+            to_duration(self.max),
+            to_duration(self.std_dev),
+            to_duration(self.p95),
+            to_duration(self.p99),
+            self.warmup,
+            self.mild_outliers,
+            self.severe_outliers
+        )
+    }
+}
+
+/// Linearly interpolated quantile of sorted values.
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let rank = q * (sorted.len() - 1) as f64;
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - rank.floor())
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn to_duration(nanos: f64) -> Duration {
+    Duration::from_nanos(nanos.round() as u64)
+}
+
+fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
+    s.split(postfix).next()?.parse().ok()
+}
+
+/// Parses a duration printed with `{:?}` into nanoseconds.
+pub fn parse_duration(s: &str) -> Option<f64> {
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    match s {
+        s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
+        s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
+        s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
+        s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{parse_duration, Stats};
+
+    fn samples(nanos: &[u64]) -> Vec<Duration> {
+        nanos.iter().map(|n| Duration::from_nanos(*n)).collect()
+    }
+
+    #[test]
+    fn computes_statistics() {
+        let stats = Stats::from_samples(&samples(&[10, 20, 30, 40, 50]), 2).unwrap();
+
+        assert_eq!(stats.mean, 30.0);
+        assert_eq!(stats.median, 30.0);
+        assert_eq!(stats.min, 10.0);
+        assert_eq!(stats.max, 50.0);
+        assert!((stats.std_dev - 15.811_388).abs() < 1e-5);
+        assert_eq!(stats.p95, 48.0);
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.warmup, 2);
+        assert_eq!(stats.outliers(), 0);
+    }
+
+    #[test]
+    fn classifies_outliers() {
+        let mut nanos = vec![100; 20];
+        nanos.extend([101, 99, 102, 98]);
+        // q1 = 100, q3 = 100, any deviation beyond the fences counts.
+        nanos.extend([500, 103]);
+
+        let stats = Stats::from_samples(&samples(&nanos), 0).unwrap();
+        assert_eq!(stats.severe_outliers, 6);
+        assert_eq!(stats.mild_outliers, 0);
+
+        let stats =
+            Stats::from_samples(&samples(&[10, 11, 12, 13, 14, 15, 16, 17, 18, 24]), 0).unwrap();
+        assert_eq!(stats.mild_outliers, 1);
+        assert_eq!(stats.severe_outliers, 0);
+    }
+
+    #[test]
+    fn handles_empty_samples() {
+        assert_eq!(Stats::from_samples(&[], 0), None);
+    }
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("74.13ns"), Some(74.13));
+        assert_eq!(parse_duration("2µs"), Some(2_000.0));
+        assert_eq!(parse_duration("1.5ms"), Some(1_500_000.0));
+        assert_eq!(parse_duration("2s"), Some(2_000_000_000.0));
+    }
+}
@@ -0,0 +1,439 @@
+/// Ledger of all answers submitted for a puzzle, stored in `data/submissions/DD.jsonl`.
+///
+/// Every submission is appended with the server's verdict. Before submitting, the ledger is used to
+/// refuse answers that can not be right: answers that were rejected before, answers outside of the
+/// bounds given by "too high" / "too low" verdicts, and any answer while the server would still
+/// make us wait. Each refused submission saves a lockout.
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::str::FromStr;
+use std::time::Duration;
+
+use tinyjson::JsonValue;
+
+use crate::template::aoc_client::SubmitOutcome;
+use crate::template::Puzzle;
+
+/// Time the server makes us wait after a wrong answer, for the first few wrong answers of a part.
+const WRONG_ANSWER_COOLDOWN: Duration = Duration::from_secs(60);
+
+/// Number of wrong answers to a part after which the server makes us wait longer.
+const LENIENT_WRONG_ANSWERS: usize = 4;
+
+/// Time the server makes us wait after each wrong answer beyond [`LENIENT_WRONG_ANSWERS`].
+const REPEATED_WRONG_ANSWER_COOLDOWN: Duration = Duration::from_secs(5 * 60);
+
+#[must_use]
+pub fn get_path_for_submissions(puzzle: Puzzle) -> String {
+    puzzle.data_path("submissions", "jsonl")
+}
+
+/// A single submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    pub part: u8,
+    pub answer: String,
+    pub outcome: SubmitOutcome,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+}
+
+impl Submission {
+    pub fn is_wrong(&self) -> bool {
+        matches!(
+            self.outcome,
+            SubmitOutcome::TooHigh | SubmitOutcome::TooLow | SubmitOutcome::Incorrect
+        )
+    }
+
+    /// The time until which the server does not accept answers after this submission.
+    /// `wrong_answers` is the number of wrong answers to the part up to and including this one.
+    fn cooldown_until(&self, wrong_answers: usize) -> u64 {
+        match self.outcome {
+            _ if self.is_wrong() && wrong_answers > LENIENT_WRONG_ANSWERS => {
+                self.timestamp + REPEATED_WRONG_ANSWER_COOLDOWN.as_secs()
+            }
+            _ if self.is_wrong() => self.timestamp + WRONG_ANSWER_COOLDOWN.as_secs(),
+            SubmitOutcome::Wait(wait) => self.timestamp + wait.as_secs(),
+            _ => self.timestamp,
+        }
+    }
+}
+
+/// Answers of a part that can be ruled out without asking the server.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Bounds {
+    /// The highest answer that is too low.
+    pub too_low: Option<i128>,
+    /// The lowest answer that is too high.
+    pub too_high: Option<i128>,
+}
+
+/// Why an answer is not submitted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Refusal {
+    /// The part was solved before, with this answer.
+    Solved(String),
+    /// The same answer was rejected before.
+    KnownWrong,
+    /// The answer is at least as high as an answer that is too high.
+    TooHigh(i128),
+    /// The answer is at most as low as an answer that is too low.
+    TooLow(i128),
+    /// The server would not accept an answer before this much time has passed.
+    Cooldown(Duration),
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::Solved(answer) => write!(f, "the part was solved with {answer:?} already."),
+            Refusal::KnownWrong => write!(f, "the answer was submitted before and is wrong."),
+            Refusal::TooHigh(bound) => write!(f, "answers of {bound} and above are too high."),
+            Refusal::TooLow(bound) => write!(f, "answers of {bound} and below are too low."),
+            Refusal::Cooldown(wait) => write!(
+                f,
+                "the server does not accept answers for another {}m {}s.",
+                wait.as_secs() / 60,
+                wait.as_secs() % 60
+            ),
+        }
+    }
+}
+
+/// All submissions of a puzzle, oldest first.
+#[derive(Clone, Debug, Default)]
+pub struct Ledger {
+    pub submissions: Vec<Submission>,
+}
+
+impl Ledger {
+    /// Reads the ledger of `puzzle`. Returns an empty ledger if nothing was submitted yet.
+    pub fn read(puzzle: Puzzle) -> Result<Self, String> {
+        let path = get_path_for_submissions(puzzle);
+
+        match fs::read_to_string(&path) {
+            Ok(s) => parse(&s)
+                .map(|submissions| Ledger { submissions })
+                .map_err(|e| format!("{path}: {e}")),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Ledger::default()),
+            Err(e) => Err(format!("{path}: {e}")),
+        }
+    }
+
+    /// Appends a submission to the ledger file of `puzzle`.
+    pub fn append(puzzle: Puzzle, submission: &Submission) -> io::Result<()> {
+        let line = JsonValue::from(submission)
+            .stringify()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+
+        fs::create_dir_all(format!("{}/submissions", puzzle.year.data_dir()))?;
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(get_path_for_submissions(puzzle))?;
+
+        writeln!(file, "{line}")
+    }
+
+    pub fn bounds(&self, part: u8) -> Bounds {
+        let mut bounds = Bounds::default();
+
+        for submission in self.submissions.iter().filter(|s| s.part == part) {
+            let Ok(answer) = submission.answer.trim().parse::<i128>() else {
+                continue;
+            };
+
+            match submission.outcome {
+                SubmitOutcome::TooLow => {
+                    bounds.too_low = bounds.too_low.max(Some(answer));
+                }
+                SubmitOutcome::TooHigh => {
+                    bounds.too_high = Some(bounds.too_high.map_or(answer, |b| b.min(answer)));
+                }
+                _ => {}
+            }
+        }
+
+        bounds
+    }
+
+    /// Checks whether `answer` is worth submitting for `part` at `now` (seconds since the unix epoch).
+    pub fn check(&self, part: u8, answer: &str, now: u64) -> Result<(), Refusal> {
+        let submissions = self.submissions.iter().filter(|s| s.part == part);
+
+        if let Some(solved) = submissions
+            .clone()
+            .find(|s| s.outcome == SubmitOutcome::Correct)
+        {
+            return Err(Refusal::Solved(solved.answer.clone()));
+        }
+
+        if submissions
+            .filter(|s| s.is_wrong())
+            .any(|s| s.answer == answer)
+        {
+            return Err(Refusal::KnownWrong);
+        }
+
+        if let Ok(value) = answer.trim().parse::<i128>() {
+            let bounds = self.bounds(part);
+
+            match (bounds.too_low, bounds.too_high) {
+                (Some(too_low), _) if value <= too_low => return Err(Refusal::TooLow(too_low)),
+                (_, Some(too_high)) if value >= too_high => return Err(Refusal::TooHigh(too_high)),
+                _ => {}
+            }
+        }
+
+        // the cooldown applies to both parts of a puzzle, its length depends on the wrong answers
+        // to the part that caused it.
+        let mut wrong_answers: HashMap<u8, usize> = HashMap::new();
+
+        let cooldown_until = self
+            .submissions
+            .iter()
+            .map(|submission| {
+                let count = wrong_answers.entry(submission.part).or_default();
+                *count += usize::from(submission.is_wrong());
+                submission.cooldown_until(*count)
+            })
+            .max()
+            .unwrap_or_default();
+
+        if cooldown_until > now {
+            return Err(Refusal::Cooldown(Duration::from_secs(cooldown_until - now)));
+        }
+
+        Ok(())
+    }
+}
+
+fn parse(s: &str) -> Result<Vec<Submission>, String> {
+    s.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            JsonValue::from_str(line)
+                .map_err(|_| "not valid JSON.".to_string())
+                .and_then(|json| Submission::try_from(&json))
+                .map_err(|e| format!("line {}: {e}", i + 1))
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        let outcome = match value.outcome {
+            SubmitOutcome::Correct => "correct",
+            SubmitOutcome::TooHigh => "too_high",
+            SubmitOutcome::TooLow => "too_low",
+            SubmitOutcome::Incorrect => "incorrect",
+            SubmitOutcome::Wait(_) => "wait",
+            SubmitOutcome::AlreadySolved => "already_solved",
+        };
+
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert("outcome".into(), JsonValue::String(outcome.into()));
+
+        if let SubmitOutcome::Wait(wait) = value.outcome {
+            #[allow(clippy::cast_precision_loss)]
+            map.insert("wait_secs".into(), JsonValue::Number(wait.as_secs() as f64));
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected submission to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .copied()
+                .ok_or(format!("expected submission.{key} to be a number."))
+        };
+
+        let outcome = match json.get("outcome").and_then(|v| v.get::<String>()) {
+            Some(outcome) if outcome == "correct" => SubmitOutcome::Correct,
+            Some(outcome) if outcome == "too_high" => SubmitOutcome::TooHigh,
+            Some(outcome) if outcome == "too_low" => SubmitOutcome::TooLow,
+            Some(outcome) if outcome == "incorrect" => SubmitOutcome::Incorrect,
+            Some(outcome) if outcome == "wait" => {
+                SubmitOutcome::Wait(Duration::from_secs(number("wait_secs")? as u64))
+            }
+            Some(outcome) if outcome == "already_solved" => SubmitOutcome::AlreadySolved,
+            _ => return Err("expected submission.outcome to be a verdict.".into()),
+        };
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("expected submission.answer to be a string.")?
+            .clone();
+
+        Ok(Submission {
+            part: number("part")? as u8,
+            answer,
+            outcome,
+            timestamp: number("timestamp")? as u64,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{parse, Bounds, Ledger, Refusal, Submission};
+    use crate::template::aoc_client::SubmitOutcome;
+    use tinyjson::JsonValue;
+
+    fn submission(part: u8, answer: &str, outcome: SubmitOutcome, timestamp: u64) -> Submission {
+        Submission {
+            part,
+            answer: answer.into(),
+            outcome,
+            timestamp,
+        }
+    }
+
+    fn ledger() -> Ledger {
+        Ledger {
+            submissions: vec![
+                submission(1, "500", SubmitOutcome::TooHigh, 1000),
+                submission(1, "100", SubmitOutcome::TooLow, 1100),
+                submission(1, "300", SubmitOutcome::TooHigh, 1200),
+                submission(1, "abc", SubmitOutcome::Incorrect, 1300),
+            ],
+        }
+    }
+
+    #[test]
+    fn derives_bounds() {
+        assert_eq!(
+            ledger().bounds(1),
+            Bounds {
+                too_low: Some(100),
+                too_high: Some(300)
+            }
+        );
+        assert_eq!(ledger().bounds(2), Bounds::default());
+    }
+
+    #[test]
+    fn refuses_impossible_answers() {
+        let ledger = ledger();
+        let later = 2000;
+
+        assert_eq!(ledger.check(1, "abc", later), Err(Refusal::KnownWrong));
+        assert_eq!(ledger.check(1, "300", later), Err(Refusal::KnownWrong));
+        assert_eq!(ledger.check(1, "400", later), Err(Refusal::TooHigh(300)));
+        assert_eq!(ledger.check(1, "-5", later), Err(Refusal::TooLow(100)));
+        assert_eq!(ledger.check(1, "250", later), Ok(()));
+        assert_eq!(ledger.check(2, "400", later), Ok(()));
+    }
+
+    #[test]
+    fn enforces_cooldowns() {
+        let mut ledger = ledger();
+
+        assert_eq!(
+            ledger.check(1, "250", 1330),
+            Err(Refusal::Cooldown(Duration::from_secs(30)))
+        );
+        assert_eq!(
+            ledger.check(2, "1", 1330),
+            Err(Refusal::Cooldown(Duration::from_secs(30)))
+        );
+
+        ledger.submissions.push(submission(
+            1,
+            "250",
+            SubmitOutcome::Wait(Duration::from_secs(300)),
+            1400,
+        ));
+        assert_eq!(
+            ledger.check(1, "250", 1500),
+            Err(Refusal::Cooldown(Duration::from_secs(200)))
+        );
+        assert_eq!(ledger.check(1, "250", 1700), Ok(()));
+    }
+
+    #[test]
+    fn lengthens_cooldowns_after_repeated_wrong_answers() {
+        let mut ledger = ledger();
+
+        ledger
+            .submissions
+            .push(submission(2, "7", SubmitOutcome::Incorrect, 1400));
+        assert_eq!(
+            ledger.check(2, "8", 1430),
+            Err(Refusal::Cooldown(Duration::from_secs(30)))
+        );
+
+        // the fifth wrong answer to part 1.
+        ledger
+            .submissions
+            .push(submission(1, "200", SubmitOutcome::TooLow, 1500));
+        assert_eq!(
+            ledger.check(2, "8", 1600),
+            Err(Refusal::Cooldown(Duration::from_secs(200)))
+        );
+        assert_eq!(ledger.check(2, "8", 1800), Ok(()));
+    }
+
+    #[test]
+    fn refuses_solved_parts() {
+        let mut ledger = ledger();
+        ledger
+            .submissions
+            .push(submission(1, "200", SubmitOutcome::Correct, 1400));
+
+        assert_eq!(
+            ledger.check(1, "201", 2000),
+            Err(Refusal::Solved("200".into()))
+        );
+    }
+
+    #[test]
+    fn round_trips_json() {
+        let submissions = vec![
+            submission(2, "#.\n.#", SubmitOutcome::Correct, 1),
+            submission(1, "42", SubmitOutcome::Wait(Duration::from_secs(90)), 2),
+        ];
+
+        let lines: Vec<String> = submissions
+            .iter()
+            .map(|s| JsonValue::from(s).stringify().unwrap())
+            .collect();
+
+        assert_eq!(parse(&lines.join("\n")).unwrap(), submissions);
+        assert!(parse("{\"part\": 1}").is_err());
+    }
+}
+
+/* -------------------------------------------------------------------------- */
@@ -0,0 +1,173 @@
+/// Templates for the solution files that `cargo scaffold` creates.
+///
+/// The built-in templates live in `src/templates`. A file `templates/NAME.txt` in the workspace
+/// (or in the directory set with `AOC_TEMPLATE_DIR`) adds a template of that name, or replaces the
+/// built-in one. Templates are filled in by replacing these placeholders:
+///
+/// - `%PUZZLE%`: the arguments of the `solution!` macro, e.g. `5` or `year = 2020, 5`.
+/// - `%DAY_NUMBER%`, `%YEAR%`: the day without padding and the year of the puzzle.
+/// - `%TITLE%`: the heading of the puzzle, e.g. `Day 5: Sunny with a Chance of Asteroids`.
+/// - `%URL%`: the puzzle's page on the puzzle site.
+/// - `%PART_ONE_ANSWER%`, `%PART_TWO_ANSWER%`: the expected results of the example tests.
+/// - `%PART_TWO_EXAMPLE%`: the call that reads the example of part two.
+use std::collections::BTreeSet;
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::{env, fs, io};
+
+pub const DEFAULT_TEMPLATE: &str = "plain";
+
+const BUILTIN_TEMPLATES: [(&str, &str); 4] = [
+    ("plain", include_str!("../templates/plain.txt")),
+    ("grid", include_str!("../templates/grid.txt")),
+    ("intcode", include_str!("../templates/intcode.txt")),
+    ("parse", include_str!("../templates/parse.txt")),
+];
+
+#[derive(Debug)]
+pub enum TemplateError {
+    Unknown {
+        name: String,
+        available: Vec<String>,
+    },
+    Io(io::Error),
+}
+
+impl Display for TemplateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TemplateError::Unknown { name, available } => write!(
+                f,
+                "unknown template `{name}`, expected one of: {}.",
+                available.join(", ")
+            ),
+            TemplateError::Io(e) => write!(f, "could not read template: {e}."),
+        }
+    }
+}
+
+/// Values of the placeholders, see the module docs.
+pub struct Placeholders {
+    pub puzzle: String,
+    pub day_number: u8,
+    pub year: u16,
+    pub title: String,
+    pub url: String,
+    pub answers: [String; 2],
+    pub part_two_example: String,
+}
+
+/// Directory of the user's templates, `templates` unless set with `AOC_TEMPLATE_DIR`.
+fn user_dir() -> PathBuf {
+    env::var_os("AOC_TEMPLATE_DIR").map_or_else(|| PathBuf::from("templates"), PathBuf::from)
+}
+
+/// Names of the built-in templates and of the user's templates.
+#[must_use]
+pub fn available() -> Vec<String> {
+    let mut names: BTreeSet<String> = BUILTIN_TEMPLATES
+        .iter()
+        .map(|(name, _)| (*name).to_string())
+        .collect();
+
+    if let Ok(entries) = fs::read_dir(user_dir()) {
+        names.extend(entries.filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let is_template = path.extension().is_some_and(|ext| ext == "txt");
+            is_template.then(|| path.file_stem()?.to_str().map(String::from))?
+        }));
+    }
+
+    names.into_iter().collect()
+}
+
+/// Reads the template `name`, preferring the user's template over a built-in one.
+pub fn load(name: &str) -> Result<String, TemplateError> {
+    match fs::read_to_string(user_dir().join(format!("{name}.txt"))) {
+        Ok(template) => return Ok(template),
+        Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(TemplateError::Io(e)),
+        Err(_) => {}
+    }
+
+    BUILTIN_TEMPLATES
+        .iter()
+        .find(|(builtin, _)| *builtin == name)
+        .map(|(_, template)| (*template).to_string())
+        .ok_or_else(|| TemplateError::Unknown {
+            name: name.into(),
+            available: available(),
+        })
+}
+
+#[must_use]
+pub fn render(template: &str, values: &Placeholders) -> String {
+    template
+        .replace("%PUZZLE%", &values.puzzle)
+        .replace("%DAY_NUMBER%", &values.day_number.to_string())
+        .replace("%YEAR%", &values.year.to_string())
+        .replace("%TITLE%", &values.title)
+        .replace("%URL%", &values.url)
+        .replace("%PART_ONE_ANSWER%", &values.answers[0])
+        .replace("%PART_TWO_ANSWER%", &values.answers[1])
+        .replace("%PART_TWO_EXAMPLE%", &values.part_two_example)
+}
+
+/// Reads the title from the heading of a downloaded puzzle description,
+/// e.g. `## --- Day 5: Sunny with a Chance of Asteroids ---`.
+#[must_use]
+pub fn title(markdown: &str) -> Option<String> {
+    let heading = markdown.lines().next()?.strip_prefix("## ")?;
+    let title = heading.trim_matches(|c: char| c == '-' || c.is_whitespace());
+    (!title.is_empty()).then(|| title.to_string())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{load, render, title, Placeholders, BUILTIN_TEMPLATES};
+
+    fn placeholders() -> Placeholders {
+        Placeholders {
+            puzzle: "5".into(),
+            day_number: 5,
+            year: 2019,
+            title: "Day 5: Sunny with a Chance of Asteroids".into(),
+            url: "https://adventofcode.com/2019/day/5".into(),
+            answers: ["Some(1)".into(), "None".into()],
+            part_two_example: "read_file(\"examples\", PUZZLE)".into(),
+        }
+    }
+
+    #[test]
+    fn renders_builtin_templates() {
+        for (name, template) in BUILTIN_TEMPLATES {
+            let module = render(template, &placeholders());
+
+            assert!(!module.contains('%'), "placeholder left in {name}");
+            assert!(module.contains("advent_of_code::solution!(5"));
+            assert!(module.starts_with("//! # Day 5: Sunny with a Chance of Asteroids\n"));
+            assert!(module.contains("assert_eq!(result, Some(1));"));
+        }
+    }
+
+    #[test]
+    fn loads_builtin_templates() {
+        assert!(load("intcode").unwrap().contains("IntcodeComputer"));
+        assert!(load("missing")
+            .unwrap_err()
+            .to_string()
+            .contains("grid, intcode, parse, plain"));
+    }
+
+    #[test]
+    fn reads_titles() {
+        assert_eq!(
+            title("## --- Day 5: Sunny with a Chance of Asteroids ---\n\nText"),
+            Some("Day 5: Sunny with a Chance of Asteroids".into())
+        );
+        assert_eq!(title("Text"), None);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
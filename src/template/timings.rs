@@ -1,9 +1,31 @@
 use std::{collections::HashMap, fs, io::Error, str::FromStr};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::memory::MemoryStats;
+use crate::template::record::PARSE;
+use crate::template::stats::{parse_duration, Stats};
+use crate::template::{Day, Year};
+
+/// Path of the stored timings of `year`.
+#[must_use]
+pub fn get_path_for_timings(year: Year) -> String {
+    format!("./{}/timings.json", year.data_dir())
+}
+
+/// Path of a named baseline. Baselines are stored alongside `timings.json`.
+#[must_use]
+pub fn get_path_for_baseline(year: Year, name: &str) -> String {
+    format!("./{}/timings-{name}.json", year.data_dir())
+}
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+/// Baseline names end up in file names, so only a conservative set of characters is allowed.
+#[must_use]
+pub fn is_valid_baseline_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
@@ -11,7 +33,62 @@ pub struct Timing {
     pub day: Day,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    /// Time of the shared parse phase, absent for days that parse inside their parts.
+    pub parse: Option<String>,
     pub total_nanos: f64,
+    /// Benchmark statistics, absent for timings stored by older versions.
+    pub parse_stats: Option<Stats>,
+    pub part_1_stats: Option<Stats>,
+    pub part_2_stats: Option<Stats>,
+    /// Heap usage, only present for days profiled with `cargo time --memory`.
+    pub part_1_memory: Option<MemoryStats>,
+    pub part_2_memory: Option<MemoryStats>,
+}
+
+impl Timing {
+    /// Mean duration of a part, or of the parse phase for [`PARSE`], in nanoseconds.
+    /// Falls back to parsing the formatted timing for entries stored without statistics.
+    pub fn part_nanos(&self, part: u8) -> Option<f64> {
+        match part {
+            PARSE => self
+                .parse_stats
+                .as_ref()
+                .map(|s| s.mean)
+                .or_else(|| parse_duration(self.parse.as_ref()?)),
+            1 => self
+                .part_1_stats
+                .as_ref()
+                .map(|s| s.mean)
+                .or_else(|| parse_duration(self.part_1.as_ref()?)),
+            2 => self
+                .part_2_stats
+                .as_ref()
+                .map(|s| s.mean)
+                .or_else(|| parse_duration(self.part_2.as_ref()?)),
+            _ => None,
+        }
+    }
+
+    pub fn part_stats(&self, part: u8) -> Option<&Stats> {
+        match part {
+            PARSE => self.parse_stats.as_ref(),
+            1 => self.part_1_stats.as_ref(),
+            2 => self.part_2_stats.as_ref(),
+            _ => None,
+        }
+    }
+
+    pub fn part_memory(&self, part: u8) -> Option<&MemoryStats> {
+        match part {
+            1 => self.part_1_memory.as_ref(),
+            2 => self.part_2_memory.as_ref(),
+            _ => None,
+        }
+    }
+
+    pub fn has_memory(&self) -> bool {
+        self.part_1_memory.is_some() || self.part_2_memory.is_some()
+    }
 }
 
 /// Represents benchmark times for a set of days.
@@ -23,15 +100,27 @@ pub struct Timings {
 
 impl Timings {
     /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        self.store_to_path(&get_path_for_timings(year))
+    }
+
+    /// Dehydrate timings to a JSON file at `path`.
+    pub fn store_to_path(&self, path: &str) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let mut file = fs::File::create(path)?;
         json.format_to(&mut file)
     }
 
+    /// Rehydrate timings from a JSON file at `path`.
+    pub fn read_from_path(path: &str) -> Result<Self, String> {
+        fs::read_to_string(path)
+            .map_err(|x| format!("{path}: {x}"))
+            .and_then(Timings::try_from)
+    }
+
     /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        let s = fs::read_to_string(TIMINGS_FILE_PATH)
+    pub fn read_from_file(year: Year) -> Self {
+        let s = fs::read_to_string(get_path_for_timings(year))
             .map_err(|x| x.to_string())
             .and_then(Timings::try_from);
 
@@ -45,11 +134,21 @@ impl Timings {
     }
 
     /// Merge two sets of timings, overwriting `self` with `other` if present.
+    /// Memory profiles are opt-in, so a day's existing profile is kept if the new timing has none.
     pub fn merge(&self, new: &Self) -> Self {
         let mut data: Vec<Timing> = vec![];
 
         for timing in &new.data {
-            data.push(timing.clone());
+            let mut timing = timing.clone();
+
+            if !timing.has_memory() {
+                if let Some(old) = self.data.iter().find(|t| t.day == timing.day) {
+                    timing.part_1_memory = old.part_1_memory;
+                    timing.part_2_memory = old.part_2_memory;
+                }
+            }
+
+            data.push(timing);
         }
 
         for timing in &self.data {
@@ -58,7 +157,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|t| t.day);
         Timings { data }
     }
 
@@ -67,6 +166,10 @@ impl Timings {
         self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
     }
 
+    pub fn has_day(&self, day: Day) -> bool {
+        self.data.iter().any(|t| t.day == day)
+    }
+
     pub fn is_day_complete(&self, day: Day) -> bool {
         self.data
             .iter()
@@ -94,7 +197,14 @@ impl TryFrom<String> for Timings {
 
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+        Timings::try_from(&json)
+    }
+}
+
+impl TryFrom<&JsonValue> for Timings {
+    type Error = String;
 
+    fn try_from(json: &JsonValue) -> Result<Self, Self::Error> {
         let json_data = json
             .get::<HashMap<String, JsonValue>>()
             .ok_or("expected JSON document to be an object.")?
@@ -121,9 +231,18 @@ impl From<&Timing> for JsonValue {
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
+        let parse = value.parse.clone().map(JsonValue::String);
         let part_1 = value.part_1.clone().map(JsonValue::String);
         let part_2 = value.part_2.clone().map(JsonValue::String);
 
+        map.insert(
+            "parse".into(),
+            match parse {
+                Some(x) => x,
+                None => JsonValue::Null,
+            },
+        );
+
         map.insert(
             "part_1".into(),
             match part_1 {
@@ -140,6 +259,33 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        for (key, stats) in [
+            ("parse_stats", &value.parse_stats),
+            ("part_1_stats", &value.part_1_stats),
+            ("part_2_stats", &value.part_2_stats),
+        ] {
+            map.insert(
+                key.into(),
+                match stats {
+                    Some(x) => JsonValue::from(x),
+                    None => JsonValue::Null,
+                },
+            );
+        }
+
+        for (key, memory) in [
+            ("part_1_memory", &value.part_1_memory),
+            ("part_2_memory", &value.part_2_memory),
+        ] {
+            map.insert(
+                key.into(),
+                match memory {
+                    Some(x) => JsonValue::from(x),
+                    None => JsonValue::Null,
+                },
+            );
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -168,16 +314,151 @@ impl TryFrom<&JsonValue> for Timing {
             .map(|v| if v.is_null() { None } else { v.get::<String>() })
             .ok_or("Expected timing.part_2 to be null or string.")?;
 
+        // timings stored by older versions have no parse phase.
+        let parse = match json.get("parse") {
+            None | Some(JsonValue::Null) => None,
+            Some(JsonValue::String(parse)) => Some(parse.clone()),
+            Some(_) => return Err("Expected timing.parse to be null or string.".into()),
+        };
+
         let total_nanos = json
             .get("total_nanos")
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        let parse_stats = |key: &str| match json.get(key) {
+            None | Some(JsonValue::Null) => Ok(None),
+            Some(v) => Stats::try_from(v)
+                .map(Some)
+                .map_err(|e| format!("Expected timing.{key} to be null or statistics: {e}")),
+        };
+
+        let parse_memory = |key: &str| match json.get(key) {
+            None | Some(JsonValue::Null) => Ok(None),
+            Some(v) => MemoryStats::try_from(v)
+                .map(Some)
+                .map_err(|e| format!("Expected timing.{key} to be null or memory statistics: {e}")),
+        };
+
         Ok(Timing {
             day,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
+            parse,
             total_nanos,
+            parse_stats: parse_stats("parse_stats")?,
+            part_1_stats: parse_stats("part_1_stats")?,
+            part_2_stats: parse_stats("part_2_stats")?,
+            part_1_memory: parse_memory("part_1_memory")?,
+            part_2_memory: parse_memory("part_2_memory")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Stats> for JsonValue {
+    fn from(value: &Stats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("mean".into(), JsonValue::Number(value.mean));
+        map.insert("median".into(), JsonValue::Number(value.median));
+        map.insert("min".into(), JsonValue::Number(value.min));
+        map.insert("max".into(), JsonValue::Number(value.max));
+        map.insert("std_dev".into(), JsonValue::Number(value.std_dev));
+        map.insert("p95".into(), JsonValue::Number(value.p95));
+        map.insert("p99".into(), JsonValue::Number(value.p99));
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert("warmup".into(), JsonValue::Number(value.warmup as f64));
+        map.insert(
+            "mild_outliers".into(),
+            JsonValue::Number(value.mild_outliers as f64),
+        );
+        map.insert(
+            "severe_outliers".into(),
+            JsonValue::Number(value.severe_outliers as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Stats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected statistics to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("expected `{key}` to be a number."))
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let count = |key: &str| number(key).map(|x| x as usize);
+
+        Ok(Stats {
+            mean: number("mean")?,
+            median: number("median")?,
+            min: number("min")?,
+            max: number("max")?,
+            std_dev: number("std_dev")?,
+            p95: number("p95")?,
+            p99: number("p99")?,
+            samples: count("samples")?,
+            warmup: count("warmup")?,
+            mild_outliers: count("mild_outliers")?,
+            severe_outliers: count("severe_outliers")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&MemoryStats> for JsonValue {
+    fn from(value: &MemoryStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "peak_bytes".into(),
+            JsonValue::Number(value.peak_bytes as f64),
+        );
+        map.insert(
+            "total_allocations".into(),
+            JsonValue::Number(value.total_allocations as f64),
+        );
+        map.insert(
+            "total_bytes".into(),
+            JsonValue::Number(value.total_bytes as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for MemoryStats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected memory statistics to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let count = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .map(|x| x as u64)
+                .ok_or(format!("expected `{key}` to be a number."))
+        };
+
+        Ok(MemoryStats {
+            peak_bytes: count("peak_bytes")?,
+            total_allocations: count("total_allocations")?,
+            total_bytes: count("total_bytes")?,
         })
     }
 }
@@ -197,19 +478,37 @@ mod tests {
                     day: day!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    parse: None,
                     total_nanos: 3e+10,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    parse: None,
                     total_nanos: 7e+10,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: None,
+                    parse: None,
                     total_nanos: 4e+10,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
                 },
             ],
         }
@@ -228,6 +527,31 @@ mod tests {
             assert_eq!(timing.part_1, Some("1ms".to_string()));
             assert_eq!(timing.part_2, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
+            assert!(timing.part_1_stats.is_none());
+        }
+
+        #[test]
+        fn handles_json_statistics() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "part_1_stats": { "mean": 1000000, "median": 990000, "min": 950000, "max": 1500000, "std_dev": 20000, "p95": 1100000, "p99": 1200000, "samples": 1000, "warmup": 100, "mild_outliers": 4, "severe_outliers": 1 }, "part_2_stats": null }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let stats = timing.part_1_stats.as_ref().unwrap();
+            assert_eq!(stats.median, 990_000_f64);
+            assert_eq!(stats.samples, 1000);
+            assert_eq!(stats.severe_outliers, 1);
+            assert!(timing.part_2_stats.is_none());
+        }
+
+        #[test]
+        fn handles_json_memory() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "part_1_memory": { "peak_bytes": 4096, "total_allocations": 12, "total_bytes": 10000 }, "part_2_memory": null }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let memory = timing.part_memory(1).unwrap();
+            assert_eq!(memory.peak_bytes, 4096);
+            assert_eq!(memory.total_allocations, 12);
+            assert_eq!(memory.total_bytes, 10000);
+            assert!(timing.part_memory(2).is_none());
         }
 
         #[test]
@@ -275,6 +599,29 @@ mod tests {
         }
     }
 
+    mod part_nanos {
+        use crate::{day, template::timings::Timing};
+
+        #[test]
+        fn falls_back_to_formatted_timings() {
+            let timing = Timing {
+                day: day!(1),
+                part_1: Some("1.5ms".into()),
+                part_2: None,
+                parse: None,
+                total_nanos: 1_500_000_f64,
+                parse_stats: None,
+                part_1_stats: None,
+                part_2_stats: None,
+                part_1_memory: None,
+                part_2_memory: None,
+            };
+
+            assert_eq!(timing.part_nanos(1), Some(1_500_000_f64));
+            assert_eq!(timing.part_nanos(2), None);
+        }
+    }
+
     mod is_day_complete {
         use crate::{
             day,
@@ -288,11 +635,17 @@ mod tests {
                     day: day!(1),
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
+                    parse: None,
                     total_nanos: 3_000_000_000_f64,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -302,11 +655,17 @@ mod tests {
                     day: day!(1),
                     part_1: Some("1ms".into()),
                     part_2: None,
+                    parse: None,
                     total_nanos: 1_000_000_000_f64,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -316,17 +675,24 @@ mod tests {
                     day: day!(1),
                     part_1: None,
                     part_2: None,
+                    parse: None,
                     total_nanos: 0.0,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
     mod merge {
         use crate::{
             day,
+            template::memory::MemoryStats,
             template::timings::{Timing, Timings},
         };
 
@@ -340,7 +706,13 @@ mod tests {
                     day: day!(3),
                     part_1: None,
                     part_2: None,
+                    parse: None,
                     total_nanos: 0_f64,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
                 }],
             };
             let merged = timings.merge(&other);
@@ -360,7 +732,13 @@ mod tests {
                     day: day!(2),
                     part_1: None,
                     part_2: None,
+                    parse: None,
                     total_nanos: 0_f64,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
                 }],
             };
             let merged = timings.merge(&other);
@@ -372,6 +750,28 @@ mod tests {
             assert_eq!(merged.data[2].day, day!(4));
         }
 
+        #[test]
+        fn keeps_memory_of_unprofiled_timings() {
+            let memory = MemoryStats {
+                peak_bytes: 1024,
+                total_allocations: 3,
+                total_bytes: 2048,
+            };
+
+            let mut timings = get_mock_timings();
+            timings.data[1].part_1_memory = Some(memory);
+
+            let merged = timings.merge(&get_mock_timings());
+            assert_eq!(merged.data[1].part_1_memory, Some(memory));
+
+            let mut profiled = get_mock_timings();
+            profiled.data[1].part_2_memory = Some(memory);
+
+            let merged = timings.merge(&profiled);
+            assert_eq!(merged.data[1].part_1_memory, None);
+            assert_eq!(merged.data[1].part_2_memory, Some(memory));
+        }
+
         #[test]
         fn handles_empty_timings() {
             let timings = Timings::default();
@@ -0,0 +1,134 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// The year this workspace was set up for, taken from `AOC_YEAR` at build time.
+const PRIMARY_YEAR: u16 = include!(concat!(env!("OUT_DIR"), "/primary_year.rs"));
+
+/// A year in which advent of code took place (i.e. 2015 or later).
+///
+/// The puzzles of the primary year (`AOC_YEAR`) use the flat layout of a single-year workspace,
+/// `src/bin/DD.rs` and `data/inputs/DD.txt`. Puzzles of any other year live in `src/bin/YYYY-DD.rs`
+/// and in a data directory of their own, e.g. `data/2020/inputs/DD.txt`.
+///
+/// ```
+/// # use advent_of_code::template::Year;
+/// let year = Year::new(2020).unwrap();
+/// assert_eq!(year.to_string(), "2020")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if advent of code took place in it,
+    /// returns [`None`] otherwise.
+    pub fn new(year: u16) -> Option<Self> {
+        if year < 2015 {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __new_unchecked(year: u16) -> Self {
+        Self(year)
+    }
+
+    /// The year this workspace was set up for, configured with `AOC_YEAR`.
+    /// Falls back to the latest event if `AOC_YEAR` was not set when building.
+    pub const fn primary() -> Self {
+        Self(PRIMARY_YEAR)
+    }
+
+    /// Returns `true` if this is the year this workspace was set up for.
+    pub fn is_primary(self) -> bool {
+        self == Self::primary()
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+
+    /// Directory that holds the inputs, examples, answers and timings of this year.
+    #[must_use]
+    pub fn data_dir(self) -> String {
+        if self.is_primary() {
+            "data".into()
+        } else {
+            format!("data/{self}")
+        }
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a year of 2015 or later")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`Year`] value in a const context.
+#[macro_export]
+macro_rules! year {
+    ($year:expr) => {{
+        const _ASSERT: () = assert!(
+            $year >= 2015,
+            concat!(
+                "invalid year `",
+                $year,
+                "`, expecting a value of 2015 or later"
+            ),
+        );
+        $crate::template::Year::__new_unchecked($year)
+    }};
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Year;
+
+    #[test]
+    fn parses_years() {
+        assert_eq!("2020".parse::<Year>().ok(), Some(Year(2020)));
+        assert!("2014".parse::<Year>().is_err());
+        assert!("20".parse::<Year>().is_err());
+    }
+
+    #[test]
+    fn data_dirs() {
+        assert_eq!(Year::primary().data_dir(), "data");
+
+        let other = Year(Year::primary().into_inner() + 1);
+        assert_eq!(other.data_dir(), format!("data/{other}"));
+    }
+}
+
+/* -------------------------------------------------------------------------- */